@@ -0,0 +1,896 @@
+use anyhow::bail;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt::Display};
+
+/// The mode of the event loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Normal,
+    Search,
+    Slice,
+    GoTo,
+    SetMark,
+    JumpToMark,
+    XAxis,
+    IgnoreValues,
+}
+
+impl Mode {
+    pub fn accepts_text(&self) -> bool {
+        matches!(
+            self,
+            Self::Search | Self::Slice | Self::GoTo | Self::XAxis | Self::IgnoreValues
+        )
+    }
+
+    pub fn accepts_mark(&self) -> bool {
+        matches!(self, Self::SetMark | Self::JumpToMark)
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "Normal"),
+            Self::Search => write!(f, "Search"),
+            Self::Slice => write!(f, "Slice"),
+            Self::GoTo => write!(f, "Go to Path"),
+            Self::SetMark => write!(f, "Set Bookmark"),
+            Self::JumpToMark => write!(f, "Jump to Bookmark"),
+            Self::XAxis => write!(f, "Plot X Axis"),
+            Self::IgnoreValues => write!(f, "Ignore Values"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Always,
+    TreeFocused,
+    EntityFocused,
+    Searching,
+    Plotting,
+    Histogram,
+    Computing,
+}
+
+impl Condition {
+    fn holds(&self, context: &Context) -> bool {
+        match self {
+            Self::Always => true,
+            Self::TreeFocused => !context.entity_focused,
+            Self::EntityFocused => context.entity_focused,
+            Self::Searching => context.searching,
+            Self::Plotting => context.entity_focused && context.plotting,
+            Self::Histogram => context.entity_focused && context.histogram,
+            Self::Computing => context.computing,
+        }
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        match self {
+            Self::Always => None,
+            Self::TreeFocused => Some("tree focused"),
+            Self::EntityFocused => Some("entity focused"),
+            Self::Searching => Some("while searching"),
+            Self::Plotting => Some("plot shown"),
+            Self::Histogram => Some("histogram shown"),
+            Self::Computing => Some("while computing statistics"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Context {
+    pub entity_focused: bool,
+    pub searching: bool,
+    pub plotting: bool,
+    pub histogram: bool,
+    pub computing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    SwitchFocus,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PageUp,
+    PageDown,
+    Collapse,
+    Expand,
+    CollapseAll,
+    ExpandAll,
+    JumpToOriginal,
+    JumpToDefaultPlot,
+    GoToPath,
+    SetMark,
+    JumpToMark,
+    JumpBack,
+    JumpForward,
+    Search,
+    ClearSearch,
+    NextMatch,
+    PreviousMatch,
+    ToggleFilter,
+    ToggleFuzzy,
+    PreviousAxis,
+    NextAxis,
+    DecrementIndex,
+    IncrementIndex,
+    DisplayAsRows,
+    DisplayAsColumns,
+    EditSlice,
+    TogglePlot,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    SetXAxis,
+    NextColormap,
+    NextScaling,
+    ToggleHistogram,
+    MoreBins,
+    FewerBins,
+    ToggleLogCounts,
+    ToggleHistogramScope,
+    SetIgnoredValues,
+    ComputeStatistics,
+    CancelStatistics,
+    Confirm,
+    Complete,
+    Cancel,
+    DeleteChar,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub modes: Vec<Mode>,
+    pub keys: Vec<(KeyCode, KeyModifiers)>,
+    pub condition: Condition,
+    pub action: Action,
+    pub description: &'static str,
+    /// Whether the binding is listed in the status line.
+    pub status: bool,
+}
+
+impl Binding {
+    fn new(
+        modes: &[Mode],
+        keys: &[(KeyCode, KeyModifiers)],
+        action: Action,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            modes: modes.to_vec(),
+            keys: keys.to_vec(),
+            condition: Condition::Always,
+            action,
+            description,
+            status: false,
+        }
+    }
+
+    fn when(mut self, condition: Condition) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    fn in_status(mut self) -> Self {
+        self.status = true;
+        self
+    }
+
+    pub fn key_names(&self) -> String {
+        self.keys
+            .iter()
+            .map(|&(code, modifiers)| key_name(code, modifiers))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    pub fn primary_key_name(&self) -> String {
+        self.keys
+            .first()
+            .map(|&(code, modifiers)| key_name(code, modifiers))
+            .unwrap_or_default()
+    }
+
+    pub fn full_description(&self) -> String {
+        match self.condition.description() {
+            Some(condition) => format!("{} ({condition})", self.description),
+            None => self.description.to_string(),
+        }
+    }
+}
+
+/// The key bindings in order of precedence.
+#[derive(Debug, Clone)]
+pub struct Bindings(pub Vec<Binding>);
+
+impl Bindings {
+    pub fn action(&self, mode: Mode, context: &Context, key: KeyEvent) -> Option<Action> {
+        let key = normalize(key.code, key.modifiers);
+        self.available(mode, context)
+            .find(|binding| {
+                binding
+                    .keys
+                    .iter()
+                    .any(|&(code, modifiers)| normalize(code, modifiers) == key)
+            })
+            .map(|binding| binding.action)
+    }
+
+    /// The bindings which apply in the mode, in order of precedence.
+    pub fn available<'a>(
+        &'a self,
+        mode: Mode,
+        context: &'a Context,
+    ) -> impl Iterator<Item = &'a Binding> {
+        self.0.iter().filter(move |binding| {
+            binding.modes.contains(&mode) && binding.condition.holds(context)
+        })
+    }
+
+    /// Replaces the keys of each action. Plain characters are not bound where text is entered,
+    /// where the default keys which are not characters are kept if no others are given.
+    pub fn remap(&mut self, keys: &HashMap<Action, Vec<String>>) -> Result<(), anyhow::Error> {
+        let is_text = |&(code, modifiers): &(KeyCode, KeyModifiers)| {
+            matches!(code, KeyCode::Char(_)) && (modifiers - KeyModifiers::SHIFT).is_empty()
+        };
+        let mut remapped = keys.iter().collect::<Vec<_>>();
+        remapped.sort_by_key(|&(action, _)| action);
+        for (action, names) in remapped {
+            let keys = names
+                .iter()
+                .map(|name| parse_key(name))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut unbound = keys.clone();
+            let mut description = "";
+            for binding in self
+                .0
+                .iter_mut()
+                .filter(|binding| binding.action == *action)
+            {
+                let accepts_input = binding
+                    .modes
+                    .iter()
+                    .any(|mode| mode.accepts_text() || mode.accepts_mark());
+                let defaults = std::mem::take(&mut binding.keys);
+                binding.keys = keys
+                    .iter()
+                    .filter(|key| !(accepts_input && is_text(key)))
+                    .copied()
+                    .collect();
+                if accepts_input && binding.keys.is_empty() && !keys.is_empty() {
+                    binding.keys = defaults.into_iter().filter(|key| !is_text(key)).collect();
+                }
+                unbound.retain(|key| !binding.keys.contains(key));
+                description = binding.description;
+            }
+            if let Some(&(code, modifiers)) = unbound.first() {
+                bail!(
+                    "'{}' cannot be bound to {description}, as characters are entered as text \
+                     where it applies",
+                    key_name(code, modifiers)
+                );
+            }
+        }
+        self.check_shadowed(keys)
+    }
+
+    /// Reports keys of the remapped actions which another binding always takes precedence over.
+    fn check_shadowed(&self, remapped: &HashMap<Action, Vec<String>>) -> Result<(), anyhow::Error> {
+        for (idx, later) in self.0.iter().enumerate() {
+            for earlier in &self.0[..idx] {
+                let shadows = earlier.action != later.action
+                    && (remapped.contains_key(&earlier.action)
+                        || remapped.contains_key(&later.action))
+                    && (earlier.condition == Condition::Always
+                        || earlier.condition == later.condition);
+                let mode = later.modes.iter().find(|mode| earlier.modes.contains(mode));
+                let key = later.keys.iter().find(|&&(code, modifiers)| {
+                    earlier.keys.iter().any(|&(other, other_modifiers)| {
+                        normalize(other, other_modifiers) == normalize(code, modifiers)
+                    })
+                });
+                if let (true, Some(mode), Some(&(code, modifiers))) = (shadows, mode, key) {
+                    bail!(
+                        "'{}' is bound to both {} and {} in {mode} mode",
+                        key_name(code, modifiers),
+                        earlier.description,
+                        later.description
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use KeyCode::{
+            Backspace, Char, Down, Enter, Esc, Left, PageDown, PageUp, Right, Tab, Up, F,
+        };
+        use Mode::{GoTo, IgnoreValues, JumpToMark, Normal, Search, SetMark, Slice, XAxis};
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
+        const CONTROL: KeyModifiers = KeyModifiers::CONTROL;
+        const ALT: KeyModifiers = KeyModifiers::ALT;
+        const ALL: &[Mode] = &[
+            Normal,
+            Search,
+            Slice,
+            GoTo,
+            SetMark,
+            JumpToMark,
+            XAxis,
+            IgnoreValues,
+        ];
+        const INPUT: &[Mode] = &[Search, Slice, GoTo, XAxis, IgnoreValues];
+        Self(vec![
+            Binding::new(ALL, &[(F(1), NONE)], Action::Help, "Help"),
+            Binding::new(&[Normal], &[(Char('?'), NONE)], Action::Help, "Help").in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Esc, NONE)],
+                Action::CancelStatistics,
+                "Cancel Statistics",
+            )
+            .when(Condition::Computing)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Esc, NONE)],
+                Action::ClearSearch,
+                "Clear Search",
+            )
+            .when(Condition::Searching)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Esc, NONE), (Char('q'), NONE)],
+                Action::Quit,
+                "Quit",
+            )
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Up, NONE), (Char('k'), NONE)],
+                Action::MoveUp,
+                "Move Up",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Down, NONE), (Char('j'), NONE)],
+                Action::MoveDown,
+                "Move Down",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(PageUp, NONE)],
+                Action::PageUp,
+                "Move Up (Page)",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(PageDown, NONE)],
+                Action::PageDown,
+                "Move Down (Page)",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Left, NONE), (Char('h'), NONE)],
+                Action::Collapse,
+                "Collapse Group",
+            )
+            .when(Condition::TreeFocused),
+            Binding::new(
+                &[Normal],
+                &[(Right, NONE), (Char('l'), NONE)],
+                Action::Expand,
+                "Expand Group",
+            )
+            .when(Condition::TreeFocused),
+            Binding::new(
+                &[Normal],
+                &[(Left, NONE), (Char('h'), NONE)],
+                Action::MoveLeft,
+                "Move Left",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Right, NONE), (Char('l'), NONE)],
+                Action::MoveRight,
+                "Move Right",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Left, SHIFT), (Char('H'), NONE)],
+                Action::CollapseAll,
+                "Collapse All",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Right, SHIFT), (Char('L'), NONE)],
+                Action::ExpandAll,
+                "Expand All",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Enter, NONE)],
+                Action::JumpToOriginal,
+                "Jump to Original",
+            )
+            .when(Condition::TreeFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('d'), NONE)],
+                Action::JumpToDefaultPlot,
+                "Jump to Default Plot",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Tab, NONE)],
+                Action::SwitchFocus,
+                "Switch Focus",
+            )
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char(':'), NONE)],
+                Action::GoToPath,
+                "Go to Path",
+            )
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('m'), NONE)],
+                Action::SetMark,
+                "Set Bookmark",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Char('\''), NONE)],
+                Action::JumpToMark,
+                "Jump to Bookmark",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Char('o'), CONTROL), (Left, ALT)],
+                Action::JumpBack,
+                "Jump Back",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Char('i'), ALT), (Right, ALT)],
+                Action::JumpForward,
+                "Jump Forward",
+            ),
+            Binding::new(&[Normal], &[(Char('/'), NONE)], Action::Search, "Search").in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('n'), NONE)],
+                Action::NextMatch,
+                "Next Match",
+            )
+            .when(Condition::TreeFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('N'), NONE)],
+                Action::PreviousMatch,
+                "Previous Match",
+            )
+            .when(Condition::TreeFocused),
+            Binding::new(
+                &[Normal, Search],
+                &[(Char('f'), CONTROL)],
+                Action::ToggleFilter,
+                "Toggle Filter",
+            ),
+            Binding::new(
+                &[Normal, Search],
+                &[(Char('t'), CONTROL)],
+                Action::ToggleFuzzy,
+                "Toggle Fuzzy",
+            ),
+            Binding::new(
+                &[Normal],
+                &[(Char(','), NONE)],
+                Action::PreviousAxis,
+                "Select Previous Axis",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('.'), NONE)],
+                Action::NextAxis,
+                "Select Next Axis",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('['), NONE)],
+                Action::DecrementIndex,
+                "Decrement Axis Index",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char(']'), NONE)],
+                Action::IncrementIndex,
+                "Increment Axis Index",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('r'), NONE)],
+                Action::DisplayAsRows,
+                "Display Axis as Rows",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('c'), NONE)],
+                Action::DisplayAsColumns,
+                "Display Axis as Columns",
+            )
+            .when(Condition::EntityFocused),
+            Binding::new(
+                &[Normal],
+                &[(Char('s'), NONE)],
+                Action::EditSlice,
+                "Enter Slice Expression",
+            )
+            .when(Condition::EntityFocused)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('p'), NONE)],
+                Action::TogglePlot,
+                "Toggle Plot",
+            )
+            .when(Condition::EntityFocused)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('+'), NONE), (Char('='), NONE)],
+                Action::ZoomIn,
+                "Zoom In",
+            )
+            .when(Condition::Plotting),
+            Binding::new(&[Normal], &[(Char('-'), NONE)], Action::ZoomOut, "Zoom Out")
+                .when(Condition::Plotting),
+            Binding::new(
+                &[Normal],
+                &[(Char('0'), NONE)],
+                Action::ResetZoom,
+                "Reset Zoom",
+            )
+            .when(Condition::Plotting),
+            Binding::new(
+                &[Normal],
+                &[(Char('x'), NONE)],
+                Action::SetXAxis,
+                "Set X Axis Dataset",
+            )
+            .when(Condition::Plotting)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('g'), NONE)],
+                Action::NextColormap,
+                "Next Colormap",
+            )
+            .when(Condition::Plotting),
+            Binding::new(
+                &[Normal],
+                &[(Char('e'), NONE)],
+                Action::NextScaling,
+                "Next Color Scaling",
+            )
+            .when(Condition::Plotting),
+            Binding::new(
+                &[Normal],
+                &[(Char('b'), NONE)],
+                Action::ToggleHistogram,
+                "Toggle Histogram",
+            )
+            .when(Condition::EntityFocused)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('+'), NONE), (Char('='), NONE)],
+                Action::MoreBins,
+                "More Bins",
+            )
+            .when(Condition::Histogram),
+            Binding::new(
+                &[Normal],
+                &[(Char('-'), NONE)],
+                Action::FewerBins,
+                "Fewer Bins",
+            )
+            .when(Condition::Histogram),
+            Binding::new(
+                &[Normal],
+                &[(Char('e'), NONE)],
+                Action::ToggleLogCounts,
+                "Toggle Log Counts",
+            )
+            .when(Condition::Histogram),
+            Binding::new(
+                &[Normal],
+                &[(Char('a'), NONE)],
+                Action::ToggleHistogramScope,
+                "Toggle Whole Dataset",
+            )
+            .when(Condition::Histogram),
+            Binding::new(
+                &[Normal],
+                &[(Char('i'), NONE)],
+                Action::SetIgnoredValues,
+                "Set Ignored Values",
+            )
+            .when(Condition::Histogram)
+            .in_status(),
+            Binding::new(
+                &[Normal],
+                &[(Char('S'), NONE)],
+                Action::ComputeStatistics,
+                "Compute Statistics",
+            ),
+            Binding::new(INPUT, &[(Enter, NONE)], Action::Confirm, "Confirm").in_status(),
+            Binding::new(&[GoTo], &[(Tab, NONE)], Action::Complete, "Complete Path").in_status(),
+            Binding::new(
+                &[
+                    Search,
+                    Slice,
+                    GoTo,
+                    SetMark,
+                    JumpToMark,
+                    XAxis,
+                    IgnoreValues,
+                ],
+                &[(Esc, NONE)],
+                Action::Cancel,
+                "Cancel",
+            )
+            .in_status(),
+            Binding::new(
+                INPUT,
+                &[(Backspace, NONE)],
+                Action::DeleteChar,
+                "Delete Character",
+            ),
+        ])
+    }
+}
+
+/// Terminals differ in whether they report shift for upper case and symbol characters.
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    match code {
+        KeyCode::Char(_) => (code, modifiers - KeyModifiers::SHIFT),
+        _ => (code, modifiers),
+    }
+}
+
+/// Parses a key such as `q`, `ctrl+f` or `shift+left`.
+pub fn parse_key(name: &str) -> Result<(KeyCode, KeyModifiers), anyhow::Error> {
+    let (modifier_names, key) = match name.rsplit_once('+') {
+        Some((modifier_names, "")) => (modifier_names.strip_suffix('+'), "+"),
+        Some((modifier_names, key)) => (Some(modifier_names), key),
+        None => (None, name),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for modifier in modifier_names
+        .into_iter()
+        .flat_map(|names| names.split('+'))
+    {
+        modifiers |= match modifier.trim().to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => bail!("Unknown modifier '{modifier}' in key '{name}'"),
+        };
+    }
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(char), None) => KeyCode::Char(char),
+        _ => match key.trim().to_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "space" => KeyCode::Char(' '),
+            lowercase => match lowercase.strip_prefix('f').map(str::parse::<u8>) {
+                Some(Ok(number)) if (1..=12).contains(&number) => KeyCode::F(number),
+                _ => bail!("Unknown key '{key}' in key '{name}'"),
+            },
+        },
+    };
+    Ok((code, modifiers))
+}
+
+/// Formats a key for display, such as `Ctrl + F`.
+fn key_name(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let (code, modifiers) = normalize(code, modifiers);
+    let mut name = String::new();
+    for (modifier, modifier_name) in [
+        (KeyModifiers::CONTROL, "Ctrl + "),
+        (KeyModifiers::ALT, "Alt + "),
+        (KeyModifiers::SHIFT, "Shift + "),
+    ] {
+        if modifiers.contains(modifier) {
+            name.push_str(modifier_name);
+        }
+    }
+    match code {
+        KeyCode::Char(' ') => name.push_str("Space"),
+        KeyCode::Char(char) if modifiers.is_empty() => name.push(char),
+        KeyCode::Char(char) => name.push(char.to_ascii_uppercase()),
+        KeyCode::F(number) => name.push_str(&format!("F{number}")),
+        KeyCode::PageUp => name.push_str("Page Up"),
+        KeyCode::PageDown => name.push_str("Page Down"),
+        KeyCode::Backspace => name.push_str("Backspace"),
+        KeyCode::Enter => name.push_str("Enter"),
+        KeyCode::Esc => name.push_str("Esc"),
+        KeyCode::Tab => name.push_str("Tab"),
+        code => name.push_str(&format!("{code:?}")),
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remapped(action: Action, keys: &[&str]) -> HashMap<Action, Vec<String>> {
+        HashMap::from([(action, keys.iter().map(|key| key.to_string()).collect())])
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys_with_modifiers() {
+        assert_eq!(
+            parse_key("q").unwrap(),
+            (KeyCode::Char('q'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("Ctrl+f").unwrap(),
+            (KeyCode::Char('f'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse_key("ctrl + alt + pagedown").unwrap(),
+            (KeyCode::PageDown, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            parse_key("f12").unwrap(),
+            (KeyCode::F(12), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("space").unwrap(),
+            (KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("+").unwrap(),
+            (KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("ctrl++").unwrap(),
+            (KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!(parse_key("f13").is_err());
+        assert!(parse_key("pgdn").is_err());
+        assert!(parse_key("super+q").is_err());
+    }
+
+    #[test]
+    fn remaps_keys_in_every_mode_of_the_action() {
+        let mut bindings = Bindings::default();
+        bindings
+            .remap(&remapped(Action::Quit, &["ctrl+c"]))
+            .unwrap();
+        let context = Context::default();
+        assert_eq!(
+            bindings.action(
+                Mode::Normal,
+                &context,
+                key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+            ),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            bindings.action(
+                Mode::Normal,
+                &context,
+                key(KeyCode::Char('q'), KeyModifiers::NONE)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_characters_where_text_is_entered() {
+        let mut bindings = Bindings::default();
+        assert!(bindings.remap(&remapped(Action::Confirm, &["y"])).is_err());
+        assert!(bindings
+            .remap(&remapped(Action::Confirm, &["ctrl+y"]))
+            .is_ok());
+    }
+
+    #[test]
+    fn keeps_default_keys_where_text_is_entered() {
+        let mut bindings = Bindings::default();
+        bindings.remap(&remapped(Action::Help, &["u"])).unwrap();
+        let context = Context::default();
+        let action = |mode, code| bindings.action(mode, &context, key(code, KeyModifiers::NONE));
+        assert_eq!(action(Mode::Normal, KeyCode::Char('u')), Some(Action::Help));
+        assert_eq!(action(Mode::Normal, KeyCode::Char('?')), None);
+        assert_eq!(action(Mode::Search, KeyCode::Char('u')), None);
+        assert_eq!(action(Mode::Search, KeyCode::F(1)), Some(Action::Help));
+    }
+
+    #[test]
+    fn reports_the_first_invalid_action() {
+        // Each map iterates in a different order, which should not change the error.
+        for _ in 0..8 {
+            let keys = HashMap::from([
+                (Action::Complete, vec!["t".to_string()]),
+                (Action::Confirm, vec!["y".to_string()]),
+                (Action::Cancel, vec!["c".to_string()]),
+            ]);
+            let err = Bindings::default().remap(&keys).unwrap_err();
+            assert!(err.to_string().contains("Confirm"), "{err}");
+        }
+    }
+
+    #[test]
+    fn rejects_keys_shadowed_by_another_action() {
+        let mut bindings = Bindings::default();
+        assert!(bindings.remap(&remapped(Action::Search, &["q"])).is_err());
+    }
+
+    #[test]
+    fn default_keys_are_not_shadowed() {
+        let bindings = Bindings::default();
+        let actions = bindings.0.iter().map(|binding| (binding.action, vec![]));
+        assert!(bindings.check_shadowed(&actions.collect()).is_ok());
+    }
+
+    #[test]
+    fn formats_key_names() {
+        assert_eq!(
+            key_name(KeyCode::Char('f'), KeyModifiers::CONTROL),
+            "Ctrl + F"
+        );
+        assert_eq!(key_name(KeyCode::Char('N'), KeyModifiers::SHIFT), "N");
+        assert_eq!(key_name(KeyCode::Char(' '), KeyModifiers::NONE), "Space");
+        assert_eq!(key_name(KeyCode::PageDown, KeyModifiers::NONE), "Page Down");
+    }
+}
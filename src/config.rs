@@ -0,0 +1,168 @@
+use crate::{bindings::Action, graphics::GraphicsSetting};
+use anyhow::Context;
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf, sync::OnceLock};
+
+/// The configuration directory, such as `~/.config/nexplore`.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("nexplore"))
+}
+
+/// The user configuration, read from `config.toml` in the configuration directory.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keys: HashMap<Action, Vec<String>>,
+    pub theme: Theme,
+    pub graphics: GraphicsSetting,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, anyhow::Error> {
+        let Some(path) = config_dir().map(|dir| dir.join("config.toml")) else {
+            return Ok(Self::default());
+        };
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Could not read {}", path.display()))
+            }
+        };
+        toml::from_str(&contents).with_context(|| format!("Invalid config in {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub group: Color,
+    pub dataset: Color,
+    pub link: Color,
+    pub broken_link: Color,
+    pub error: Color,
+    /// Defaults to the color of the selected item.
+    pub selection: Option<Color>,
+    pub search_match: Color,
+    pub classes: HashMap<String, ClassStyle>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassStyle {
+    pub color: Option<Color>,
+    pub glyph: Option<String>,
+}
+
+const DEFAULT_CLASS_COLORS: &[(&str, Color)] = &[
+    ("NXentry", Color::LightMagenta),
+    ("NXinstrument", Color::LightBlue),
+    ("NXdetector", Color::Cyan),
+    ("NXsample", Color::LightYellow),
+    ("NXmonitor", Color::LightCyan),
+    ("NXdata", Color::Magenta),
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            group: Color::Blue,
+            dataset: Color::Green,
+            link: Color::DarkGray,
+            broken_link: Color::Red,
+            error: Color::LightRed,
+            selection: None,
+            search_match: Color::Yellow,
+            classes: HashMap::new(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn class_color(&self, class: &str) -> Color {
+        self.classes
+            .get(class)
+            .and_then(|style| style.color)
+            .or_else(|| {
+                DEFAULT_CLASS_COLORS
+                    .iter()
+                    .find(|(name, _)| *name == class)
+                    .map(|(_, color)| *color)
+            })
+            .unwrap_or(self.group)
+    }
+
+    pub fn class_glyph(&self, class: &str) -> Option<&str> {
+        self.classes.get(class)?.glyph.as_deref()
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Sets the theme, which may only be done once.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_colors_by_name_index_and_hex() {
+        let config: Config = toml::from_str(
+            r##"
+            [theme]
+            group = "light_blue"
+            dataset = "208"
+            link = "#ff8800"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(config.theme.group, Color::LightBlue);
+        assert_eq!(config.theme.dataset, Color::Indexed(208));
+        assert_eq!(config.theme.link, Color::Rgb(255, 136, 0));
+        assert_eq!(config.theme.error, Theme::default().error);
+    }
+
+    #[test]
+    fn parses_keys_by_action() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys]
+            toggle_filter = ["ctrl+l"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.keys[&Action::ToggleFilter], vec!["ctrl+l"]);
+    }
+
+    #[test]
+    fn rejects_unknown_actions_and_fields() {
+        assert!(toml::from_str::<Config>("[keys]\nteleport = [\"t\"]").is_err());
+        assert!(toml::from_str::<Config>("[theme]\nbackground = \"black\"").is_err());
+    }
+
+    #[test]
+    fn falls_back_to_the_group_color_for_other_classes() {
+        let config: Config = toml::from_str(
+            r#"
+            [theme.classes.NXdetector]
+            color = "red"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.theme.class_color("NXdetector"), Color::Red);
+        assert_eq!(config.theme.class_color("NXentry"), Color::LightMagenta);
+        assert_eq!(config.theme.class_color("NXother"), config.theme.group);
+    }
+}
@@ -0,0 +1,308 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crossterm::{cursor::MoveTo, queue};
+use ratatui::layout::Rect;
+use serde::Deserialize;
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+/// How long to wait for the terminal to answer the graphics query.
+const QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+const DEFAULT_CELL_SIZE: (u16, u16) = (8, 16);
+const KITTY_CHUNK_SIZE: usize = 4096;
+const SIXEL_LEVELS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+    Iterm,
+}
+
+/// The configured graphics protocol, where `auto` detects it from the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphicsSetting {
+    #[default]
+    Auto,
+    Kitty,
+    Sixel,
+    Iterm,
+    /// Always draw images with characters.
+    #[serde(rename = "none")]
+    Disabled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub area: Rect,
+    pub image: RgbImage,
+}
+
+/// Draws images with a graphics protocol, outside of the buffer drawn by ratatui.
+#[derive(Debug)]
+pub struct Graphics {
+    protocol: Option<Protocol>,
+    /// Images are not drawn again unless they change.
+    shown: Option<Placement>,
+}
+
+impl Graphics {
+    /// Must be called while the terminal is in raw mode and before any events are read.
+    pub fn new(setting: GraphicsSetting) -> Self {
+        let protocol = match setting {
+            GraphicsSetting::Auto => detect(),
+            GraphicsSetting::Kitty => Some(Protocol::Kitty),
+            GraphicsSetting::Sixel => Some(Protocol::Sixel),
+            GraphicsSetting::Iterm => Some(Protocol::Iterm),
+            GraphicsSetting::Disabled => None,
+        };
+        Self {
+            protocol,
+            shown: None,
+        }
+    }
+
+    /// The size of a cell in pixels, if images can be drawn with a graphics protocol.
+    pub fn cell_size(&self) -> Option<(u16, u16)> {
+        self.protocol?;
+        let size = crossterm::terminal::window_size().ok();
+        Some(
+            size.filter(|size| size.width > 0 && size.height > 0 && size.columns > 0)
+                .map_or(DEFAULT_CELL_SIZE, |size| {
+                    (size.width / size.columns, size.height / size.rows.max(1))
+                }),
+        )
+    }
+
+    /// Draws the image, replacing the one previously drawn. Kitty images must be deleted, whereas
+    /// others are removed by ratatui drawing over them.
+    pub fn show(
+        &mut self,
+        out: &mut impl Write,
+        placement: Option<Placement>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(protocol) = self.protocol else {
+            return Ok(());
+        };
+        if placement == self.shown {
+            return Ok(());
+        }
+        if protocol == Protocol::Kitty && self.shown.is_some() {
+            out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+        }
+        if let Some(placement) = placement.as_ref() {
+            queue!(out, MoveTo(placement.area.x, placement.area.y))?;
+            let sequence = match protocol {
+                Protocol::Kitty => kitty(placement)?,
+                Protocol::Sixel => sixel(&placement.image),
+                Protocol::Iterm => iterm(placement)?,
+            };
+            out.write_all(sequence.as_bytes())?;
+        }
+        out.flush()?;
+        self.shown = placement;
+        Ok(())
+    }
+}
+
+fn detect() -> Option<Protocol> {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    if var("TERM_PROGRAM") == "iTerm.app" || var("LC_TERMINAL") == "iTerm2" {
+        return Some(Protocol::Iterm);
+    }
+    if !var("KITTY_WINDOW_ID").is_empty() || var("TERM") == "xterm-kitty" {
+        return Some(Protocol::Kitty);
+    }
+    let response = query()?;
+    if response.contains("_Gi=31;OK") {
+        return Some(Protocol::Kitty);
+    }
+    // The primary device attributes list the features of the terminal, where 4 is sixel.
+    let attributes = response
+        .split("\x1b[?")
+        .nth(1)?
+        .split('c')
+        .next()?
+        .split(';')
+        .collect::<Vec<_>>();
+    attributes.contains(&"4").then_some(Protocol::Sixel)
+}
+
+/// Asks for kitty graphics support followed by the primary device attributes, which every
+/// terminal answers, such that reading can stop once they are received.
+#[cfg(unix)]
+fn query() -> Option<String> {
+    const STDIN: libc::c_int = 0;
+    if unsafe { libc::isatty(STDIN) } != 1 {
+        return None;
+    }
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(b"\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c")
+        .ok()?;
+    stdout.flush().ok()?;
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut response = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let mut poll = libc::pollfd {
+            fd: STDIN,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut poll, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            break;
+        }
+        let mut buffer = [0u8; 256];
+        let read = unsafe { libc::read(STDIN, buffer.as_mut_ptr().cast(), buffer.len()) };
+        if read <= 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..read as usize]);
+        let text = String::from_utf8_lossy(&response);
+        if text
+            .split("\x1b[?")
+            .nth(1)
+            .is_some_and(|attributes| attributes.contains('c'))
+        {
+            break;
+        }
+    }
+    Some(String::from_utf8_lossy(&response).into_owned())
+}
+
+/// Terminals on other platforms cannot be queried without blocking.
+#[cfg(not(unix))]
+fn query() -> Option<String> {
+    None
+}
+
+fn encode_png(image: &RgbImage) -> Result<Vec<u8>, anyhow::Error> {
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    let bytes = image.pixels.iter().flatten().copied().collect::<Vec<_>>();
+    writer.write_image_data(&bytes)?;
+    writer.finish()?;
+    Ok(data)
+}
+
+fn kitty(placement: &Placement) -> Result<String, anyhow::Error> {
+    let payload = STANDARD.encode(encode_png(&placement.image)?);
+    let chunks = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK_SIZE)
+        .collect::<Vec<_>>();
+    let mut sequence = String::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(idx + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk)?;
+        if idx == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={more};{chunk}\x1b\\",
+                placement.area.width, placement.area.height
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    Ok(sequence)
+}
+
+fn iterm(placement: &Placement) -> Result<String, anyhow::Error> {
+    let data = encode_png(&placement.image)?;
+    Ok(format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+        data.len(),
+        placement.area.width,
+        placement.area.height,
+        STANDARD.encode(&data)
+    ))
+}
+
+/// Encodes the image as sixels, with the colors quantized to a cube of 216 colors.
+fn sixel(image: &RgbImage) -> String {
+    let level = |channel: u8| (channel as usize * (SIXEL_LEVELS - 1) + 127) / 255;
+    let colors = image
+        .pixels
+        .iter()
+        .map(|[r, g, b]| (level(*r) * SIXEL_LEVELS + level(*g)) * SIXEL_LEVELS + level(*b))
+        .collect::<Vec<_>>();
+    let mut sequence = format!("\x1bP0;1q\"1;1;{};{}", image.width, image.height);
+    let percent = |level: usize| level * 100 / (SIXEL_LEVELS - 1);
+    for color in 0..SIXEL_LEVELS.pow(3) {
+        let (r, g, b) = (
+            color / (SIXEL_LEVELS * SIXEL_LEVELS),
+            color / SIXEL_LEVELS % SIXEL_LEVELS,
+            color % SIXEL_LEVELS,
+        );
+        sequence.push_str(&format!(
+            "#{color};2;{};{};{}",
+            percent(r),
+            percent(g),
+            percent(b)
+        ));
+    }
+    for band in (0..image.height).step_by(6) {
+        let rows = band..(band + 6).min(image.height);
+        let mut band_colors = rows
+            .clone()
+            .flat_map(|row| &colors[row * image.width..(row + 1) * image.width])
+            .copied()
+            .collect::<Vec<_>>();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+        for (idx, &color) in band_colors.iter().enumerate() {
+            if idx > 0 {
+                sequence.push('$');
+            }
+            sequence.push_str(&format!("#{color}"));
+            let mut run: Option<(char, usize)> = None;
+            for column in 0..image.width {
+                let bits = rows
+                    .clone()
+                    .enumerate()
+                    .filter(|&(_, row)| colors[row * image.width + column] == color)
+                    .fold(0u8, |bits, (bit, _)| bits | 1 << bit);
+                let char = (0x3f + bits) as char;
+                run = match run {
+                    Some((run_char, count)) if run_char == char => Some((char, count + 1)),
+                    Some(previous) => {
+                        push_run(&mut sequence, previous);
+                        Some((char, 1))
+                    }
+                    None => Some((char, 1)),
+                };
+            }
+            if let Some(run) = run {
+                push_run(&mut sequence, run);
+            }
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+fn push_run(sequence: &mut String, (char, count): (char, usize)) {
+    if count > 3 {
+        sequence.push_str(&format!("!{count}{char}"));
+    } else {
+        sequence.extend(std::iter::repeat_n(char, count));
+    }
+}
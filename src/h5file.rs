@@ -1,12 +1,44 @@
 use crate::widgets::tree::TreeItem;
-use anyhow::{anyhow, Context};
-use hdf5::{dataset::Layout, filters::Filter, Dataset, File, Group, LinkInfo, LinkType};
-use std::{fmt::Display, path::Path};
+use anyhow::{anyhow, bail, Context};
+use hdf5::{
+    dataset::Layout,
+    filters::Filter,
+    types::{FixedAscii, FixedUnicode, FloatSize, TypeDescriptor, VarLenAscii, VarLenUnicode},
+    Container, Dataset, Datatype, File, Group, H5Type, LinkType, Location, LocationInfo,
+};
+use hdf5_sys::{
+    h5l::{H5L_info_t, H5L_type_t, H5Lget_info, H5Lget_val, H5Lunpack_elink_val},
+    h5p::H5P_DEFAULT,
+    h5t::{H5T_class_t, H5T_order_t, H5T_str_t, H5Tget_class, H5Tget_order, H5Tget_strpad},
+};
+use ndarray::{ArrayD, IxDyn, SliceInfo, SliceInfoElem};
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::{CStr, CString},
+    fmt::Display,
+    mem::MaybeUninit,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Clone)]
 pub enum EntityInfo {
     Group(GroupInfo),
     Dataset(DatasetInfo),
+    Duplicate(DuplicateInfo),
+    Broken(BrokenLinkInfo),
+    Error(ErrorInfo),
+}
+
+impl EntityInfo {
+    pub fn name(&self) -> &str {
+        match self {
+            EntityInfo::Group(info) => &info.name,
+            EntityInfo::Dataset(info) => &info.name,
+            EntityInfo::Duplicate(info) => &info.name,
+            EntityInfo::Broken(info) => &info.name,
+            EntityInfo::Error(info) => &info.name,
+        }
+    }
 }
 
 impl From<EntityInfo> for TreeItem<'_> {
@@ -14,6 +46,9 @@ impl From<EntityInfo> for TreeItem<'_> {
         match value {
             EntityInfo::Group(info) => TreeItem::from(info),
             EntityInfo::Dataset(info) => TreeItem::from(info),
+            EntityInfo::Duplicate(info) => TreeItem::from(info),
+            EntityInfo::Broken(info) => TreeItem::from(info),
+            EntityInfo::Error(info) => TreeItem::from(info),
         }
     }
 }
@@ -23,36 +58,268 @@ pub struct GroupInfo {
     pub name: String,
     pub id: i64,
     pub link_kind: LinkKind,
-    pub entities: Vec<EntityInfo>,
+    pub attributes: Vec<AttributeInfo>,
+    /// The `NX_class` attribute of the group.
+    pub class: Option<String>,
+    /// `None` until the entities have been loaded.
+    pub entities: Option<Vec<EntityInfo>>,
+    group: Group,
 }
 
 impl GroupInfo {
-    fn try_from_group_and_link(group: Group, link: LinkInfo) -> Result<Self, anyhow::Error> {
+    fn try_from_group_and_link(group: Group, link_kind: LinkKind) -> Result<Self, anyhow::Error> {
         let name = group.name().split('/').next_back().unwrap().to_string();
         let id = group.id();
-        let entities = group
-            .iter_visit_default(Vec::new(), |group, key, link, entities| {
-                let entity = if let Ok(group) = group.group(key) {
-                    GroupInfo::try_from_group_and_link(group, link).map(EntityInfo::Group)
-                } else if let Ok(dataset) = group.dataset(key) {
-                    Ok(EntityInfo::Dataset(DatasetInfo::from_dataset_and_link(
-                        dataset, link,
-                    )))
-                } else {
-                    Err(anyhow!("Found link to entity of unknown kind"))
-                };
-                entities.push(entity);
-                true
-            })?
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
+        let attributes = AttributeInfo::read_all(&group)?;
+        let class = attribute_value(&attributes, "NX_class").map(str::to_string);
         Ok(Self {
             name,
             id,
-            link_kind: link.link_type.into(),
-            entities,
+            link_kind,
+            attributes,
+            class,
+            entities: None,
+            group,
         })
     }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        attribute_value(&self.attributes, name)
+    }
+
+    pub fn attribute_info(&self, name: &str) -> Option<&AttributeInfo> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// The name of the dataset holding the plottable data of an `NXdata` group.
+    pub fn signal(&self) -> Option<&str> {
+        self.class
+            .as_deref()
+            .filter(|&class| class == "NXdata")
+            .and(self.attribute("signal"))
+    }
+
+    /// Opens the groups within the group, skipping links which cannot be opened.
+    pub fn subgroups(&self) -> Result<Vec<GroupInfo>, anyhow::Error> {
+        Ok(self
+            .links()?
+            .into_iter()
+            .filter_map(|(name, link_type)| {
+                let link_kind = LinkKind::read(&self.group, &name, link_type).ok()?;
+                GroupInfo::try_from_group_and_link(self.group.group(&name).ok()?, link_kind).ok()
+            })
+            .collect())
+    }
+
+    /// Opens the datasets within the group, skipping links which cannot be opened.
+    pub fn datasets(&self) -> Result<Vec<DatasetInfo>, anyhow::Error> {
+        Ok(self
+            .links()?
+            .into_iter()
+            .filter_map(|(name, link_type)| {
+                let link_kind = LinkKind::read(&self.group, &name, link_type).ok()?;
+                DatasetInfo::try_from_dataset_and_link(self.group.dataset(&name).ok()?, link_kind)
+                    .ok()
+            })
+            .collect())
+    }
+
+    fn links(&self) -> Result<Vec<(String, LinkType)>, anyhow::Error> {
+        Ok(self
+            .group
+            .iter_visit_default(Vec::new(), |_, key, link, links| {
+                links.push((key.to_string(), link.link_type));
+                true
+            })?)
+    }
+
+    /// Reads the entities within the group once. Entities loaded elsewhere in the file become
+    /// duplicates, such that cycles are not followed.
+    fn load_entities(&mut self, visited: &mut VisitedLocations) -> &mut Vec<EntityInfo> {
+        if self.entities.is_none() {
+            let entities = match self.links() {
+                Ok(links) => links
+                    .into_iter()
+                    .map(|(key, link_type)| {
+                        self.load_entity(&key, link_type, visited)
+                            .unwrap_or_else(|err| {
+                                EntityInfo::Error(ErrorInfo {
+                                    name: key,
+                                    error: format!("{err:#}"),
+                                })
+                            })
+                    })
+                    .collect(),
+                Err(err) => vec![EntityInfo::Error(ErrorInfo {
+                    name: "Unreadable Contents".to_string(),
+                    error: format!("{err:#}"),
+                })],
+            };
+            self.entities = Some(entities);
+        }
+        self.entities.get_or_insert_with(Vec::new)
+    }
+
+    fn load_entity(
+        &self,
+        key: &str,
+        link_type: LinkType,
+        visited: &mut VisitedLocations,
+    ) -> Result<EntityInfo, anyhow::Error> {
+        let link_kind = LinkKind::read(&self.group, key, link_type)?;
+        let group_err = match self.group.group(key) {
+            Ok(group) => {
+                let location = group.loc_info()?;
+                return if let Some(path) = self.original(&location, &link_kind, visited) {
+                    Ok(EntityInfo::Duplicate(DuplicateInfo {
+                        name: key.to_string(),
+                        link_kind,
+                        path,
+                        is_group: true,
+                    }))
+                } else {
+                    visited.insert(&location, group.name());
+                    GroupInfo::try_from_group_and_link(group, link_kind).map(EntityInfo::Group)
+                };
+            }
+            Err(err) => err,
+        };
+        if let Ok(dataset) = self.group.dataset(key) {
+            let location = dataset.loc_info()?;
+            if let Some(path) = self.original(&location, &link_kind, visited) {
+                Ok(EntityInfo::Duplicate(DuplicateInfo {
+                    name: key.to_string(),
+                    link_kind,
+                    path,
+                    is_group: false,
+                }))
+            } else {
+                visited.insert(&location, dataset.name());
+                DatasetInfo::try_from_dataset_and_link(dataset, link_kind).map(EntityInfo::Dataset)
+            }
+        } else if let LinkKind::Hard = link_kind {
+            Err(anyhow!("Found link to entity of unknown kind"))
+        } else {
+            Ok(EntityInfo::Broken(BrokenLinkInfo {
+                name: key.to_string(),
+                link_kind,
+                error: group_err.to_string(),
+            }))
+        }
+    }
+
+    /// The path at which the entity is shown, if elsewhere. Soft links to hard linked paths use
+    /// the target, such that the original does not depend on which link is loaded first.
+    fn original(
+        &self,
+        location: &LocationInfo,
+        link_kind: &LinkKind,
+        visited: &VisitedLocations,
+    ) -> Option<String> {
+        if let Some(path) = visited.find(location) {
+            return Some(path.to_string());
+        }
+        match link_kind {
+            LinkKind::Soft { target } => hard_path(&self.group, target),
+            _ => None,
+        }
+    }
+
+    fn load_all(&mut self, visited: &mut VisitedLocations) {
+        for entity in self.load_entities(visited) {
+            if let EntityInfo::Group(group) = entity {
+                group.load_all(visited);
+            }
+        }
+    }
+
+    fn position(&self, name: &str) -> Result<usize, anyhow::Error> {
+        self.entities
+            .as_ref()
+            .context("Group has not been loaded")?
+            .iter()
+            .position(|entity| entity.name() == name)
+            .with_context(|| format!("No entity named '{name}' in {}", self.group.name()))
+    }
+
+    fn entity(&self, index: &[usize]) -> Result<&EntityInfo, anyhow::Error> {
+        let (idx, rest) = index.split_first().context("Index was empty")?;
+        let entity = self
+            .entities
+            .as_ref()
+            .context("Group has not been loaded")?
+            .get(*idx)
+            .context("No entity at index")?;
+        if rest.is_empty() {
+            return Ok(entity);
+        }
+        match entity {
+            EntityInfo::Group(group) => group.entity(rest),
+            _ => Err(anyhow!("Cannot index into '{}'", entity.name())),
+        }
+    }
+
+    fn entity_mut(&mut self, index: &[usize]) -> Result<&mut EntityInfo, anyhow::Error> {
+        let (idx, rest) = index.split_first().context("Index was empty")?;
+        let entity = self
+            .entities
+            .as_mut()
+            .context("Group has not been loaded")?
+            .get_mut(*idx)
+            .context("No entity at index")?;
+        if rest.is_empty() {
+            return Ok(entity);
+        }
+        match entity {
+            EntityInfo::Group(group) => group.entity_mut(rest),
+            _ => Err(anyhow!("Cannot index into '{}'", entity.name())),
+        }
+    }
+}
+
+/// A link to a group or dataset which is shown elsewhere in the tree.
+#[derive(Debug, Clone)]
+pub struct DuplicateInfo {
+    pub name: String,
+    pub link_kind: LinkKind,
+    pub path: String,
+    pub is_group: bool,
+}
+
+/// A soft or external link whose target could not be opened.
+#[derive(Debug, Clone)]
+pub struct BrokenLinkInfo {
+    pub name: String,
+    pub link_kind: LinkKind,
+    pub error: String,
+}
+
+/// An entity which could not be read.
+#[derive(Debug, Clone)]
+pub struct ErrorInfo {
+    pub name: String,
+    pub error: String,
+}
+
+/// The paths at which loaded locations are shown, keyed by the file number and formatted token,
+/// as tokens cannot be hashed.
+#[derive(Debug, Clone, Default)]
+struct VisitedLocations(HashMap<(u64, String), String>);
+
+impl VisitedLocations {
+    fn key(location: &LocationInfo) -> (u64, String) {
+        (location.fileno, format!("{:?}", location.token))
+    }
+
+    fn find(&self, location: &LocationInfo) -> Option<&str> {
+        self.0.get(&Self::key(location)).map(String::as_str)
+    }
+
+    fn insert(&mut self, location: &LocationInfo, path: String) {
+        self.0.entry(Self::key(location)).or_insert(path);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -61,7 +328,10 @@ pub struct DatasetInfo {
     pub id: i64,
     pub link_type: LinkKind,
     pub shape: Vec<usize>,
+    pub datatype: DatatypeInfo,
     pub layout_info: DatasetLayoutInfo,
+    pub attributes: Vec<AttributeInfo>,
+    dataset: Dataset,
 }
 
 #[derive(Debug, Clone)]
@@ -76,10 +346,14 @@ pub enum DatasetLayoutInfo {
 }
 
 impl DatasetInfo {
-    fn from_dataset_and_link(dataset: Dataset, link: LinkInfo) -> Self {
+    fn try_from_dataset_and_link(
+        dataset: Dataset,
+        link_kind: LinkKind,
+    ) -> Result<Self, anyhow::Error> {
         let name = dataset.name().split('/').next_back().unwrap().to_string();
         let id = dataset.id();
         let shape = dataset.shape();
+        let datatype = DatatypeInfo::from_datatype(&dataset.dtype()?);
         let layout_info = match dataset.layout() {
             Layout::Compact => DatasetLayoutInfo::Compact {},
             Layout::Contiguous => DatasetLayoutInfo::Contiguous {},
@@ -89,39 +363,349 @@ impl DatasetInfo {
             },
             Layout::Virtual => DatasetLayoutInfo::Virtial {},
         };
-        Self {
+        let attributes = AttributeInfo::read_all(&dataset)?;
+        Ok(Self {
             name,
             id,
-            link_type: link.link_type.into(),
+            link_type: link_kind,
             shape,
+            datatype,
             layout_info,
+            attributes,
+            dataset,
+        })
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        attribute_value(&self.attributes, name)
+    }
+
+    pub fn attribute_info(&self, name: &str) -> Option<&AttributeInfo> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// Whether the dataset has the `signal=1` attribute of older NeXus files.
+    pub fn is_signal(&self) -> bool {
+        self.attribute("signal") == Some("1")
+    }
+
+    /// Reads and formats the values within the selection, which has one element per dimension.
+    pub fn read_values(&self, selection: &[SliceInfoElem]) -> Result<DatasetValues, anyhow::Error> {
+        read_formatted(&self.dataset, selection)
+    }
+
+    /// Whether the values of the dataset are integers or floats.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self.datatype.descriptor,
+            Ok(TypeDescriptor::Integer(_) | TypeDescriptor::Unsigned(_) | TypeDescriptor::Float(_))
+        )
+    }
+
+    /// Reads the values within the selection as floats.
+    pub fn read_numbers(&self, selection: &[SliceInfoElem]) -> Result<ArrayD<f64>, anyhow::Error> {
+        if !self.is_numeric() {
+            match &self.datatype.descriptor {
+                Ok(descriptor) => bail!("Cannot read values of type {descriptor} as numbers"),
+                Err(err) => bail!("Cannot read values of an unsupported type as numbers: {err}"),
+            }
         }
+        if self.shape.is_empty() {
+            return Ok(ArrayD::from_elem(
+                IxDyn(&[]),
+                self.dataset.read_scalar::<f64>()?,
+            ));
+        }
+        let selection = SliceInfo::<_, IxDyn, IxDyn>::try_from(selection.to_vec())?;
+        Ok(self.dataset.read_slice::<f64, _, IxDyn>(selection)?)
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct DatatypeInfo {
+    pub size: usize,
+    pub byte_order: Option<ByteOrder>,
+    pub string_padding: Option<StringPadding>,
+    pub descriptor: Result<TypeDescriptor, String>,
+}
+
+impl DatatypeInfo {
+    fn from_datatype(datatype: &Datatype) -> Self {
+        let id = datatype.id();
+        // The byte order and string padding are not exposed by the high level API.
+        let byte_order = match unsafe { H5Tget_order(id) } {
+            H5T_order_t::H5T_ORDER_LE => Some(ByteOrder::LittleEndian),
+            H5T_order_t::H5T_ORDER_BE => Some(ByteOrder::BigEndian),
+            H5T_order_t::H5T_ORDER_VAX => Some(ByteOrder::Vax),
+            H5T_order_t::H5T_ORDER_MIXED => Some(ByteOrder::Mixed),
+            _ => None,
+        };
+        let string_padding = match unsafe { H5Tget_class(id) } {
+            H5T_class_t::H5T_STRING => match unsafe { H5Tget_strpad(id) } {
+                H5T_str_t::H5T_STR_NULLTERM => Some(StringPadding::NullTerminated),
+                H5T_str_t::H5T_STR_NULLPAD => Some(StringPadding::NullPadded),
+                H5T_str_t::H5T_STR_SPACEPAD => Some(StringPadding::SpacePadded),
+                _ => None,
+            },
+            _ => None,
+        };
+        Self {
+            size: datatype.size(),
+            byte_order,
+            string_padding,
+            descriptor: datatype.to_descriptor().map_err(|err| err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+    Vax,
+    Mixed,
+}
+
+impl Display for ByteOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::LittleEndian => "Little Endian",
+            Self::BigEndian => "Big Endian",
+            Self::Vax => "VAX",
+            Self::Mixed => "Mixed",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StringPadding {
+    NullTerminated,
+    NullPadded,
+    SpacePadded,
+}
+
+impl Display for StringPadding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NullTerminated => "Null Terminated",
+            Self::NullPadded => "Null Padded",
+            Self::SpacePadded => "Space Padded",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeInfo {
+    pub name: String,
+    pub dtype: String,
+    pub shape: Vec<usize>,
+    pub value: Result<String, String>,
+    /// Empty if the value could not be read.
+    pub elements: Vec<String>,
+}
+
+impl AttributeInfo {
+    fn read_all(location: &Location) -> Result<Vec<Self>, anyhow::Error> {
+        location
+            .attr_names()?
+            .into_iter()
+            .map(|name| {
+                let attribute = location.attr(&name)?;
+                let shape = attribute.shape();
+                let selection = shape
+                    .iter()
+                    .map(|&length| SliceInfoElem::Slice {
+                        start: 0,
+                        end: Some(length as isize),
+                        step: 1,
+                    })
+                    .collect::<Vec<_>>();
+                let values = read_formatted(&attribute, &selection);
+                let value = values
+                    .as_ref()
+                    .map(|values| {
+                        if values.shape.is_empty() {
+                            values.values.join("")
+                        } else {
+                            format!("[{}]", values.values.join(", "))
+                        }
+                    })
+                    .map_err(|err| err.to_string());
+                Ok(Self {
+                    name,
+                    // Attributes of unsupported types are still listed, as with their values.
+                    dtype: attribute
+                        .dtype()
+                        .and_then(|dtype| dtype.to_descriptor())
+                        .map_or_else(|_| "Unsupported".to_string(), |dtype| dtype.to_string()),
+                    shape,
+                    value,
+                    elements: values.map(|values| values.values).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn attribute_value<'a>(attributes: &'a [AttributeInfo], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name == name)
+        .and_then(|attribute| attribute.value.as_deref().ok())
+}
+
+/// Scalar containers ignore the selection.
+fn read_formatted(
+    container: &Container,
+    selection: &[SliceInfoElem],
+) -> Result<DatasetValues, anyhow::Error> {
+    match container.dtype()?.to_descriptor()? {
+        TypeDescriptor::Integer(_) => read_values::<i64>(container, selection),
+        TypeDescriptor::Unsigned(_) => read_values::<u64>(container, selection),
+        TypeDescriptor::Float(FloatSize::U4) => read_values::<f32>(container, selection),
+        TypeDescriptor::Float(_) => read_values::<f64>(container, selection),
+        TypeDescriptor::Boolean => read_values::<bool>(container, selection),
+        TypeDescriptor::FixedAscii(_) => {
+            read_values::<FixedAscii<MAX_STRING_LENGTH>>(container, selection)
+        }
+        TypeDescriptor::FixedUnicode(_) => {
+            read_values::<FixedUnicode<MAX_STRING_LENGTH>>(container, selection)
+        }
+        TypeDescriptor::VarLenAscii => read_values::<VarLenAscii>(container, selection),
+        TypeDescriptor::VarLenUnicode => read_values::<VarLenUnicode>(container, selection),
+        descriptor => Err(anyhow!("Cannot display values of type {descriptor}")),
+    }
+}
+
+/// Longer fixed length strings are truncated.
+const MAX_STRING_LENGTH: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct DatasetValues {
+    pub shape: Vec<usize>,
+    pub values: Vec<String>,
+}
+
+fn read_values<T: H5Type + Display>(
+    container: &Container,
+    selection: &[SliceInfoElem],
+) -> Result<DatasetValues, anyhow::Error> {
+    if container.shape().is_empty() {
+        return Ok(DatasetValues {
+            shape: Vec::new(),
+            values: vec![container.read_scalar::<T>()?.to_string()],
+        });
+    }
+    let selection = SliceInfo::<_, IxDyn, IxDyn>::try_from(selection.to_vec())?;
+    let values = container.read_slice::<T, _, IxDyn>(selection)?;
+    Ok(DatasetValues {
+        shape: values.shape().to_vec(),
+        values: values.iter().map(T::to_string).collect(),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum LinkKind {
     Hard,
-    Soft,
-    External,
+    Soft { target: String },
+    External { file: String, target: String },
 }
 
-impl From<LinkType> for LinkKind {
-    fn from(value: LinkType) -> Self {
-        match value {
-            LinkType::Hard => Self::Hard,
-            LinkType::Soft => Self::Soft,
-            LinkType::External => Self::External,
+impl LinkKind {
+    fn read(group: &Group, name: &str, link_type: LinkType) -> Result<Self, anyhow::Error> {
+        match link_type {
+            LinkType::Hard => Ok(Self::Hard),
+            LinkType::Soft => Ok(Self::Soft {
+                target: CStr::from_bytes_until_nul(&read_link_value(group, name)?)?
+                    .to_string_lossy()
+                    .into_owned(),
+            }),
+            LinkType::External => {
+                let value = read_link_value(group, name)?;
+                let (mut flags, mut file, mut target) = (0, std::ptr::null(), std::ptr::null());
+                if unsafe {
+                    H5Lunpack_elink_val(
+                        value.as_ptr().cast(),
+                        value.len(),
+                        &mut flags,
+                        &mut file,
+                        &mut target,
+                    )
+                } < 0
+                {
+                    bail!("Could not unpack external link '{name}'");
+                }
+                // The unpacked strings point into the link value, which is still alive.
+                let (file, target) = unsafe { (CStr::from_ptr(file), CStr::from_ptr(target)) };
+                Ok(Self::External {
+                    file: file.to_string_lossy().into_owned(),
+                    target: target.to_string_lossy().into_owned(),
+                })
+            }
         }
     }
 }
 
+fn read_link_info(group: &Group, name: &str) -> Result<H5L_info_t, anyhow::Error> {
+    let c_name = CString::new(name)?;
+    let mut info = MaybeUninit::<H5L_info_t>::uninit();
+    if unsafe { H5Lget_info(group.id(), c_name.as_ptr(), info.as_mut_ptr(), H5P_DEFAULT) } < 0 {
+        bail!("Could not read information of link '{name}'");
+    }
+    Ok(unsafe { info.assume_init() })
+}
+
+/// Reads the raw value of a soft or external link, which is not exposed by the high level API.
+fn read_link_value(group: &Group, name: &str) -> Result<Vec<u8>, anyhow::Error> {
+    // The size of the value is held by the info of soft and external links.
+    let size = unsafe { read_link_info(group, name)?.u.val_size };
+    let c_name = CString::new(name)?;
+    let mut value = vec![0; size];
+    if unsafe {
+        H5Lget_val(
+            group.id(),
+            c_name.as_ptr(),
+            value.as_mut_ptr().cast(),
+            value.len(),
+            H5P_DEFAULT,
+        )
+    } < 0
+    {
+        bail!("Could not read value of link '{name}'");
+    }
+    Ok(value)
+}
+
+/// The absolute path of a soft link target, if every link along it is a hard link.
+fn hard_path(group: &Group, target: &str) -> Option<String> {
+    let parent = if target.starts_with('/') {
+        String::new()
+    } else {
+        group.name()
+    };
+    let mut path = String::new();
+    for name in parent
+        .split('/')
+        .chain(target.split('/'))
+        .filter(|&name| !name.is_empty() && name != ".")
+    {
+        path = format!("{path}/{name}");
+        let info = read_link_info(group, &path).ok()?;
+        if !matches!(info.type_, H5L_type_t::H5L_TYPE_HARD) {
+            return None;
+        }
+    }
+    (!path.is_empty()).then_some(path)
+}
+
 impl Display for LinkKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Self::Hard => "Hard",
-            Self::Soft => "Soft",
-            Self::External => "External",
+            Self::Soft { .. } => "Soft",
+            Self::External { .. } => "External",
         })
     }
 }
@@ -129,8 +713,12 @@ impl Display for LinkKind {
 #[derive(Debug, Clone)]
 pub struct FileInfo {
     pub name: String,
+    pub path: PathBuf,
     pub size: u64,
-    pub entities: Vec<EntityInfo>,
+    pub root: GroupInfo,
+    visited: VisitedLocations,
+    /// The open file, held such that the handles within the entities remain valid.
+    file: File,
 }
 
 impl FileInfo {
@@ -141,47 +729,179 @@ impl FileInfo {
             .context("No file in path")?
             .to_string_lossy()
             .into_owned();
+        let canonical_path = path.as_ref().canonicalize()?;
         let file = File::open(path)?;
         let size = file.size();
-        let entities = GroupInfo::try_from_group_and_link(
-            file.as_group()?,
-            LinkInfo {
-                link_type: LinkType::Hard,
-                creation_order: None,
-                is_utf8: true,
-            },
-        )?
-        .entities;
+        let group = file.as_group()?;
+        let mut visited = VisitedLocations::default();
+        visited.insert(&group.loc_info()?, group.name());
+        let mut root = GroupInfo::try_from_group_and_link(group, LinkKind::Hard)?;
+        root.load_entities(&mut visited);
 
         Ok(Self {
             name,
+            path: canonical_path,
             size,
-            entities,
+            root,
+            visited,
+            file,
         })
     }
 
     pub fn entity(&self, index: Vec<usize>) -> Result<EntityInfo, anyhow::Error> {
-        let mut indices = index.into_iter();
-        let mut entity = self
+        self.root.entity(&index).cloned()
+    }
+
+    /// Returns the entities within the group at the index as tree items, if newly loaded.
+    pub fn load_entities(
+        &mut self,
+        index: &[usize],
+    ) -> Result<Option<Vec<TreeItem<'static>>>, anyhow::Error> {
+        match self.root.entity_mut(index)? {
+            EntityInfo::Group(group) if group.entities.is_none() => Ok(Some(
+                group
+                    .load_entities(&mut self.visited)
+                    .iter()
+                    .cloned()
+                    .map(TreeItem::from)
+                    .collect(),
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn load_all(&mut self) {
+        self.root.load_all(&mut self.visited)
+    }
+
+    /// Finds the entity with the name in the group at the index, where `[]` is the root group.
+    pub fn position_in(&self, index: &[usize], name: &str) -> Result<usize, anyhow::Error> {
+        if index.is_empty() {
+            return self.root.position(name);
+        }
+        match self.root.entity(index)? {
+            EntityInfo::Group(group) => group.position(name),
+            entity => Err(anyhow!("'{}' is not a group", entity.name())),
+        }
+    }
+
+    /// The path of the entity at the index, such as `/entry/data`.
+    pub fn path_of(&self, index: &[usize]) -> Result<String, anyhow::Error> {
+        (1..=index.len()).try_fold(String::new(), |path, depth| {
+            Ok(format!(
+                "{path}/{}",
+                self.root.entity(&index[..depth])?.name()
+            ))
+        })
+    }
+
+    /// The loaded entities within the group at the index, where `[]` is the root group.
+    pub fn entities_in(&self, index: &[usize]) -> Result<&[EntityInfo], anyhow::Error> {
+        let group = if index.is_empty() {
+            &self.root
+        } else {
+            match self.root.entity(index)? {
+                EntityInfo::Group(group) => group,
+                entity => bail!("'{}' is not a group", entity.name()),
+            }
+        };
+        group
             .entities
-            .get(indices.next().context("Index was empty")?)
-            .context("No entity at index")?;
-        for idx in indices {
-            match entity {
-                EntityInfo::Group(group) => {
-                    entity = group.entities.get(idx).context("Index was empty")?
-                }
-                EntityInfo::Dataset(_) => Err(anyhow!("Cannot index into a dataset"))?,
+            .as_deref()
+            .context("Group has not been loaded")
+    }
+
+    /// Opens the group at the path without loading its ancestors.
+    pub fn group(&self, path: &str) -> Result<GroupInfo, anyhow::Error> {
+        let group = self
+            .file
+            .group(path)
+            .with_context(|| format!("No group at {path}"))?;
+        GroupInfo::try_from_group_and_link(group, LinkKind::Hard)
+    }
+
+    /// Opens the dataset at the path without loading its ancestors.
+    pub fn dataset(&self, path: &str) -> Result<DatasetInfo, anyhow::Error> {
+        let dataset = self
+            .file
+            .dataset(path)
+            .with_context(|| format!("No dataset at {path}"))?;
+        DatasetInfo::try_from_dataset_and_link(dataset, LinkKind::Hard)
+    }
+
+    /// Reads every value of the one dimensional dataset at the path.
+    pub fn read_axis(&self, path: &str) -> Result<Vec<f64>, anyhow::Error> {
+        let dataset = self.dataset(path)?;
+        if dataset.shape.len() != 1 {
+            bail!(
+                "{path} has {} dimensions, but an axis must have one",
+                dataset.shape.len()
+            );
+        }
+        if !dataset.is_numeric() {
+            match &dataset.datatype.descriptor {
+                Ok(descriptor) => bail!("{path} holds {descriptor}, but an axis must be numeric"),
+                Err(_) => bail!("{path} has an unsupported type, but an axis must be numeric"),
             }
         }
-        Ok(entity.clone())
+        let selection = [SliceInfoElem::Slice {
+            start: 0,
+            end: None,
+            step: 1,
+        }];
+        Ok(dataset.read_numbers(&selection)?.into_iter().collect())
     }
 
-    pub fn to_tree_items(&self) -> Vec<TreeItem<'_>> {
-        self.entities
+    pub fn to_tree_items(&self) -> Vec<TreeItem<'static>> {
+        self.root
+            .entities
             .iter()
+            .flatten()
             .cloned()
             .map(TreeItem::from)
             .collect::<Vec<_>>()
     }
 }
+
+/// The unexpanded groups which remain to be loaded for a search.
+#[derive(Debug, Clone, Default)]
+pub struct LoadTask {
+    pending: VecDeque<Vec<usize>>,
+    loaded: usize,
+}
+
+impl LoadTask {
+    pub fn new(file_info: &FileInfo) -> Self {
+        let mut task = Self::default();
+        task.queue_children(file_info, &[]);
+        task
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    pub fn next(&mut self) -> Option<Vec<usize>> {
+        let index = self.pending.pop_front()?;
+        self.loaded += 1;
+        Some(index)
+    }
+
+    /// Queues the unloaded groups below the group at the index.
+    pub fn queue_children(&mut self, file_info: &FileInfo, index: &[usize]) {
+        let Ok(entities) = file_info.entities_in(index) else {
+            return;
+        };
+        for (position, entity) in entities.iter().enumerate() {
+            if let EntityInfo::Group(group) = entity {
+                let mut child = index.to_vec();
+                child.push(position);
+                if group.entities.is_some() {
+                    self.queue_children(file_info, &child);
+                } else {
+                    self.pending.push_back(child);
+                }
+            }
+        }
+    }
+}
@@ -1,19 +1,39 @@
+mod bindings;
+mod config;
+mod graphics;
 mod h5file;
+mod navigation;
+mod nexus;
+mod slicing;
+mod stats;
 mod ui;
 pub mod widgets;
 
-use crate::ui::Screen;
+use crate::ui::{Panel, Screen};
 use anyhow::Context;
+use bindings::{Action, Bindings, Mode};
 use clap::Parser;
+use config::Config;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    cursor::Show,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use h5file::FileInfo;
+use graphics::{Graphics, GraphicsSetting};
+use h5file::{EntityInfo, FileInfo, LoadTask};
+use navigation::{Bookmarks, History};
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{io::Stdout, path::PathBuf, time::Duration};
-use ui::{ContentsTree, FileName, FileSize};
+use std::{
+    io::Stdout,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use ui::{ContentsTree, EntityState, FileName, FileSize, PathPrompt, ValuesState};
+use widgets::histogram::parse_ignored;
 
 /// A TUI for exploring HDF5 and NeXus files.
 #[derive(Debug, Parser)]
@@ -21,53 +41,276 @@ use ui::{ContentsTree, FileName, FileSize};
 struct Cli {
     /// The path to the HDF5 or NeXus file to open.
     path: PathBuf,
+    /// The path of an object within the file to select on opening, such as `/entry/data`.
+    object: Option<String>,
 }
 
-fn main() {
+fn main() -> Result<(), anyhow::Error> {
     let args = Cli::parse();
-    let mut terminal = setup_terminal().unwrap();
-    let file_info = FileInfo::read(args.path).unwrap();
-    run(&mut terminal, file_info).unwrap();
-    restore_terminal(&mut terminal).unwrap();
+    let config = Config::load()?;
+    let mut bindings = Bindings::default();
+    bindings
+        .remap(&config.keys)
+        .context("Invalid key bindings in config")?;
+    config::set_theme(config.theme);
+    let file_info = FileInfo::read(&args.path)
+        .with_context(|| format!("Could not open {}", args.path.display()))?;
+    let (bookmarks, bookmarks_error) = Bookmarks::load()?;
+    install_panic_hook();
+    let _guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    run(
+        &mut terminal,
+        &bindings,
+        config.graphics,
+        file_info,
+        bookmarks,
+        bookmarks_error,
+        args.object,
+    )
 }
 
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>, anyhow::Error> {
-    let mut stdout = std::io::stdout();
-    enable_raw_mode()?;
-    execute!(stdout, EnterAlternateScreen)?;
-    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+/// Restores the terminal when dropped, before any error is reported.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self, anyhow::Error> {
+        enable_raw_mode()?;
+        let guard = Self;
+        execute!(std::io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(guard)
+    }
 }
 
-fn restore_terminal(
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Err(err) = restore_terminal() {
+            eprintln!("Could not restore the terminal: {err:#}");
+        }
+    }
+}
+
+fn restore_terminal() -> Result<(), anyhow::Error> {
+    let raw_mode = disable_raw_mode();
+    execute!(
+        std::io::stdout(),
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        Show
+    )?;
+    Ok(raw_mode?)
+}
+
+/// Restores the terminal before the default panic hook prints the panic message.
+fn install_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+    }));
+}
+
+/// Selects the entity at the path, loading and expanding each of its ancestors.
+fn select_path(
+    path: &str,
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
 ) -> Result<(), anyhow::Error> {
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    Ok(terminal.show_cursor()?)
+    let index = resolve_path(path, file_info, contents_tree)?;
+    contents_tree.state.select(&index);
+    Ok(())
 }
 
-#[derive(Debug, Clone, Default)]
-enum Mode {
-    #[default]
-    Normal,
-    Search {
-        search: String,
-    },
+/// Selects the signal of the default plot, returning its axes along with the first error.
+fn open_default_plot(
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
+) -> Result<(Vec<Option<nexus::Axis>>, Option<anyhow::Error>), anyhow::Error> {
+    let plot = nexus::default_plot(file_info)?;
+    select_path(&plot.signal, file_info, contents_tree)
+        .with_context(|| format!("Could not find {}", plot.signal))?;
+    Ok(nexus::read_axes(file_info, &plot))
+}
+
+/// Finds the index of the entity at the path, following duplicates to their original.
+fn resolve_path(
+    path: &str,
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
+) -> Result<Vec<usize>, anyhow::Error> {
+    let mut index = Vec::new();
+    for name in path.split('/').filter(|name| !name.is_empty()) {
+        if let Ok(EntityInfo::Duplicate(duplicate)) = file_info.entity(index.clone()) {
+            index = resolve_path(&duplicate.path, file_info, contents_tree)?;
+        }
+        load_group(&index, file_info, contents_tree)?;
+        index.push(file_info.position_in(&index, name)?);
+    }
+    Ok(index)
+}
+
+fn load_group(
+    index: &[usize],
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
+) -> Result<(), anyhow::Error> {
+    if !index.is_empty() {
+        if let Some(children) = file_info.load_entities(index)? {
+            contents_tree.state.set_children(index, children);
+        }
+    }
+    Ok(())
+}
+
+const LOAD_STEP: Duration = Duration::from_millis(50);
+
+/// Loads queued groups for a step, returning whether any remain.
+fn load_step(
+    task: &mut LoadTask,
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
+) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < LOAD_STEP {
+        let Some(index) = task.next() else {
+            return false;
+        };
+        if let Err(err) = load_group(&index, file_info, contents_tree) {
+            contents_tree.message = Some(format!("{err:#}"));
+        }
+        task.queue_children(file_info, &index);
+    }
+    true
+}
+
+/// Completes the final component of the path up to the longest prefix of the candidates.
+fn complete_path(
+    path: &str,
+    file_info: &mut FileInfo,
+    contents_tree: &mut ContentsTree,
+) -> Result<String, anyhow::Error> {
+    let (parent, prefix) = path.rsplit_once('/').unwrap_or(("", path));
+    let mut index = resolve_path(parent, file_info, contents_tree)?;
+    if let Ok(EntityInfo::Duplicate(duplicate)) = file_info.entity(index.clone()) {
+        index = resolve_path(&duplicate.path, file_info, contents_tree)?;
+    }
+    load_group(&index, file_info, contents_tree)?;
+    let candidates = file_info
+        .entities_in(&index)?
+        .iter()
+        .filter(|entity| entity.name().starts_with(prefix))
+        .collect::<Vec<_>>();
+    let completion = match candidates.as_slice() {
+        [] => prefix.to_string(),
+        [EntityInfo::Group(group)] => format!("{}/", group.name),
+        [EntityInfo::Duplicate(duplicate)] if duplicate.is_group => {
+            format!("{}/", duplicate.name)
+        }
+        [entity] => entity.name().to_string(),
+        [first, rest @ ..] => rest
+            .iter()
+            .fold(first.name().to_string(), |common, entity| {
+                common
+                    .chars()
+                    .zip(entity.name().chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(char, _)| char)
+                    .collect()
+            }),
+    };
+    Ok(format!("{parent}/{completion}"))
+}
+
+fn show_input(
+    mode: Mode,
+    input: &str,
+    contents_tree: &mut ContentsTree,
+    entity_state: &mut EntityState,
+) {
+    match mode {
+        Mode::Search => contents_tree.state.search(Some(input.to_string())),
+        Mode::Slice => entity_state.values.edit_slice(Some(input.to_string())),
+        Mode::GoTo => contents_tree.prompt = Some(PathPrompt::new(input.to_string())),
+        Mode::XAxis => entity_state.values.edit_x_axis(Some(input.to_string())),
+        Mode::IgnoreValues => entity_state.values.edit_ignored(Some(input.to_string())),
+        _ => {}
+    }
+}
+
+/// Reads the dataset at the path as an x axis, where relative paths are resolved against the
+/// group containing the dataset at the index.
+fn read_x_axis(
+    path: &str,
+    index: Option<&[usize]>,
+    file_info: &FileInfo,
+) -> Result<Option<(String, Vec<f64>)>, anyhow::Error> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        let index = index.context("Nothing is selected")?;
+        let parent = file_info.path_of(&index[..index.len().saturating_sub(1)])?;
+        format!("{parent}/{path}")
+    };
+    let values = file_info.read_axis(&path)?;
+    Ok(Some((path, values)))
 }
 
 fn run(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    file_info: FileInfo,
+    bindings: &Bindings,
+    graphics: GraphicsSetting,
+    mut file_info: FileInfo,
+    mut bookmarks: Bookmarks,
+    bookmarks_error: Option<anyhow::Error>,
+    object: Option<String>,
 ) -> Result<(), anyhow::Error> {
     let mut mode = Mode::default();
-    let screen = Screen::default();
+    let mut input = String::new();
+    let mut show_help = false;
+    let mut screen = Screen::default();
     let file_name = FileName::new(file_info.name.clone());
     let file_size = FileSize::new(file_info.size);
     let mut contents_tree = ContentsTree::new(file_info.to_tree_items());
+    contents_tree.message = bookmarks_error.map(|err| format!("{err:#}"));
+    let mut entity_state = EntityState::default();
+    let mut selected = None;
+    let mut history = History::default();
+    let mut load_task = None;
+    let mut resizing = false;
+    let mut graphics = Graphics::new(graphics);
+    // Terminals which do not understand the graphics query may have printed part of it.
+    terminal.clear()?;
+    if let Some(object) = object {
+        select_path(&object, &mut file_info, &mut contents_tree)
+            .with_context(|| format!("Could not find {object}"))?;
+    }
     loop {
-        let entity_info = file_info
-            .entity(contents_tree.state.position().unwrap())
+        let position = contents_tree.state.position();
+        if position != selected {
+            entity_state.values = ValuesState::default();
+            selected.clone_from(&position);
+        }
+        let entity_info = position
+            .clone()
+            .map(|position| file_info.entity(position))
+            .transpose()
             .context("Could not find selected entity")?;
+        let path = position
+            .as_deref()
+            .and_then(|position| file_info.path_of(position).ok());
+        let context = bindings::Context {
+            entity_focused: entity_state.focused,
+            searching: contents_tree.state.is_searching(),
+            plotting: entity_state.values.is_plotting(),
+            histogram: entity_state.values.is_histogram(),
+            computing: entity_state.stats.is_computing(),
+        };
+        let available = bindings.available(mode, &context).collect::<Vec<_>>();
+        entity_state.heatmap.cell_size = graphics.cell_size();
         terminal.draw(|frame| {
             screen.render(
                 frame,
@@ -75,76 +318,336 @@ fn run(
                 &file_size,
                 &mut contents_tree,
                 entity_info,
-            )
+                &mut entity_state,
+            );
+            screen.render_bindings(frame, mode, &available, show_help);
         })?;
-        if event::poll(Duration::from_millis(250))? {
-            if let Event::Key(key) = event::read()? {
-                match (&mut mode, key.code, key.modifiers) {
-                    (&mut Mode::Normal, KeyCode::Esc | KeyCode::Char('q'), KeyModifiers::NONE) => {
-                        break
+        // Images are hidden while the help is shown, as they would otherwise be drawn over it.
+        let placement = entity_state.values.take_placement().filter(|_| !show_help);
+        graphics.show(terminal.backend_mut(), placement)?;
+        // Events are polled without waiting while tasks are read a block at a time.
+        let computing = entity_state.stats.step();
+        let sampling = entity_state.values.step();
+        let loading = match load_task.as_mut() {
+            Some(task) if contents_tree.state.is_searching() => {
+                load_step(task, &mut file_info, &mut contents_tree)
+            }
+            _ => false,
+        };
+        if !loading {
+            load_task = None;
+        }
+        contents_tree
+            .state
+            .set_loading(load_task.as_ref().map(LoadTask::loaded));
+        let timeout = if computing || sampling || loading {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(250)
+        };
+        if event::poll(timeout)? {
+            let event = event::read()?;
+            if let (Event::Mouse(mouse), Mode::Normal) = (&event, &mode) {
+                match (mouse.kind, screen.panel_at(mouse.column, mouse.row)) {
+                    (MouseEventKind::Down(MouseButton::Left), Some(Panel::Divider)) => {
+                        resizing = true
+                    }
+                    (MouseEventKind::Drag(MouseButton::Left), _) if resizing => {
+                        screen.resize(mouse.column)
                     }
-                    (&mut Mode::Normal, KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                    (MouseEventKind::Up(MouseButton::Left), _) => resizing = false,
+                    (MouseEventKind::Down(MouseButton::Left), Some(Panel::Contents)) => {
+                        entity_state.focused = false;
+                        if let Some(true) = contents_tree.state.click(mouse.column, mouse.row) {
+                            if let Some(index) = contents_tree.state.position() {
+                                if let Err(err) =
+                                    load_group(&index, &mut file_info, &mut contents_tree)
+                                {
+                                    contents_tree.message = Some(format!("{err:#}"));
+                                }
+                            }
+                            contents_tree.state.toggle();
+                        }
+                    }
+                    (MouseEventKind::Down(MouseButton::Left), Some(Panel::Entity)) => {
+                        entity_state.focused = true
+                    }
+                    (MouseEventKind::ScrollUp, Some(Panel::Contents)) => {
                         contents_tree.state.move_up()
                     }
-                    (&mut Mode::Normal, KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                    (MouseEventKind::ScrollDown, Some(Panel::Contents)) => {
                         contents_tree.state.move_down()
                     }
-                    (&mut Mode::Normal, KeyCode::PageUp, KeyModifiers::NONE) => {
-                        contents_tree.state.page_up()
-                    }
-                    (&mut Mode::Normal, KeyCode::PageDown, KeyModifiers::NONE) => {
-                        contents_tree.state.page_down()
-                    }
-                    (&mut Mode::Normal, KeyCode::Left | KeyCode::Char('h'), KeyModifiers::NONE) => {
-                        contents_tree.state.collapse()
-                    }
-                    (
-                        &mut Mode::Normal,
-                        KeyCode::Right | KeyCode::Char('l'),
-                        KeyModifiers::NONE,
-                    ) => contents_tree.state.expand(),
-                    (
-                        &mut Mode::Normal,
-                        KeyCode::Left | KeyCode::Char('H'),
-                        KeyModifiers::SHIFT,
-                    ) => contents_tree.state.collapse_all(),
-                    (
-                        &mut Mode::Normal,
-                        KeyCode::Right | KeyCode::Char('L'),
-                        KeyModifiers::SHIFT,
-                    ) => contents_tree.state.expand_all(),
-                    (mode, KeyCode::Char('/'), KeyModifiers::NONE)
-                        if matches!(mode, Mode::Normal) =>
+                    (MouseEventKind::ScrollUp, Some(Panel::Entity)) => {
+                        entity_state.values.move_up()
+                    }
+                    (MouseEventKind::ScrollDown, Some(Panel::Entity)) => {
+                        entity_state.values.move_down()
+                    }
+                    _ => {}
+                }
+            }
+            let Event::Key(key) = event else {
+                continue;
+            };
+            contents_tree.message = None;
+            if show_help {
+                show_help = false;
+                continue;
+            }
+            let Some(action) = bindings.action(mode, &context, key) else {
+                match (key.code, key.modifiers) {
+                    (KeyCode::Char(char), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                        if mode.accepts_text() =>
+                    {
+                        input.push(char);
+                        show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                    }
+                    (KeyCode::Char(mark), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                        if mode == Mode::SetMark =>
+                    {
+                        mode = Mode::default();
+                        let result = position
+                            .as_deref()
+                            .context("Nothing is selected")
+                            .and_then(|position| file_info.path_of(position))
+                            .and_then(|path| bookmarks.set(&file_info.path, mark, path));
+                        if let Err(err) = result {
+                            contents_tree.message = Some(format!("{err:#}"));
+                        }
+                    }
+                    (KeyCode::Char(mark), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                        if mode == Mode::JumpToMark =>
+                    {
+                        mode = Mode::default();
+                        match bookmarks.get(&file_info.path, mark).map(str::to_string) {
+                            Some(target) => {
+                                match select_path(&target, &mut file_info, &mut contents_tree) {
+                                    Ok(()) => history.record(path.clone()),
+                                    Err(err) => contents_tree.message = Some(format!("{err:#}")),
+                                }
+                            }
+                            None => contents_tree.message = Some(format!("No bookmark '{mark}'")),
+                        }
+                    }
+                    _ if mode.accepts_mark() => mode = Mode::default(),
+                    _ => {}
+                }
+                continue;
+            };
+            match action {
+                Action::Quit => break,
+                Action::Help => show_help = true,
+                Action::SwitchFocus => entity_state.focused = !entity_state.focused,
+                Action::MoveUp if entity_state.focused => entity_state.values.move_up(),
+                Action::MoveUp => contents_tree.state.move_up(),
+                Action::MoveDown if entity_state.focused => entity_state.values.move_down(),
+                Action::MoveDown => contents_tree.state.move_down(),
+                Action::MoveLeft => entity_state.values.move_left(),
+                Action::MoveRight => entity_state.values.move_right(),
+                Action::PageUp if entity_state.focused => entity_state.values.page_up(),
+                Action::PageUp => contents_tree.state.page_up(),
+                Action::PageDown if entity_state.focused => entity_state.values.page_down(),
+                Action::PageDown => contents_tree.state.page_down(),
+                Action::Collapse => contents_tree.state.collapse(),
+                Action::Expand => {
+                    if let Some(index) = position.as_deref() {
+                        if let Err(err) = load_group(index, &mut file_info, &mut contents_tree) {
+                            contents_tree.message = Some(format!("{err:#}"));
+                        }
+                    }
+                    contents_tree.state.expand()
+                }
+                Action::CollapseAll => contents_tree.state.collapse_all(),
+                Action::ExpandAll => {
+                    file_info.load_all();
+                    contents_tree.state.set_items(file_info.to_tree_items());
+                    contents_tree.state.expand_all()
+                }
+                Action::JumpToOriginal => {
+                    if let Ok(EntityInfo::Duplicate(duplicate)) =
+                        file_info.entity(position.clone().unwrap_or_default())
+                    {
+                        match select_path(&duplicate.path, &mut file_info, &mut contents_tree) {
+                            Ok(()) => history.record(path.clone()),
+                            Err(err) => contents_tree.message = Some(format!("{err:#}")),
+                        }
+                    }
+                }
+                Action::JumpToDefaultPlot => {
+                    match open_default_plot(&mut file_info, &mut contents_tree) {
+                        Ok((axes, error)) => {
+                            history.record(path.clone());
+                            // The values are reset now, rather than once the new selection is
+                            // noticed, such that the axes are kept.
+                            selected = contents_tree.state.position();
+                            entity_state.values = ValuesState::default();
+                            entity_state.values.plot_with_axes(axes);
+                            entity_state.focused = true;
+                            contents_tree.message = error.map(|err| format!("{err:#}"));
+                        }
+                        Err(err) => contents_tree.message = Some(format!("{err:#}")),
+                    }
+                }
+                Action::GoToPath => {
+                    mode = Mode::GoTo;
+                    input = "/".to_string();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                }
+                Action::SetMark => mode = Mode::SetMark,
+                Action::JumpToMark => mode = Mode::JumpToMark,
+                Action::JumpBack => {
+                    if let Some(target) = path.as_deref().and_then(|path| history.back(path)) {
+                        if let Err(err) = select_path(&target, &mut file_info, &mut contents_tree) {
+                            contents_tree.message = Some(format!("{err:#}"));
+                        }
+                    }
+                }
+                Action::JumpForward => {
+                    if let Some(target) = history.forward() {
+                        if let Err(err) = select_path(&target, &mut file_info, &mut contents_tree) {
+                            contents_tree.message = Some(format!("{err:#}"));
+                        }
+                    }
+                }
+                Action::Search => {
+                    load_task.get_or_insert_with(|| LoadTask::new(&file_info));
+                    mode = Mode::Search;
+                    input.clear();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                }
+                Action::ClearSearch => contents_tree.state.search(None),
+                Action::NextMatch => {
+                    contents_tree.state.next_match();
+                    if contents_tree.state.position() != position {
+                        history.record(path.clone());
+                    }
+                }
+                Action::PreviousMatch => {
+                    contents_tree.state.previous_match();
+                    if contents_tree.state.position() != position {
+                        history.record(path.clone());
+                    }
+                }
+                Action::ToggleFilter => contents_tree.state.toggle_filter(),
+                Action::ToggleFuzzy => contents_tree.state.toggle_search_kind(),
+                Action::PreviousAxis => entity_state.values.previous_axis(),
+                Action::NextAxis => entity_state.values.next_axis(),
+                Action::DecrementIndex => entity_state.values.step(-1),
+                Action::IncrementIndex => entity_state.values.step(1),
+                Action::DisplayAsRows => entity_state.values.display_as_rows(),
+                Action::DisplayAsColumns => entity_state.values.display_as_columns(),
+                Action::EditSlice => {
+                    mode = Mode::Slice;
+                    input = entity_state.values.slice_expression();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                }
+                Action::TogglePlot => entity_state.values.toggle_plot(),
+                Action::ZoomIn => entity_state.values.zoom_in(),
+                Action::ZoomOut => entity_state.values.zoom_out(),
+                Action::ResetZoom => entity_state.values.reset_zoom(),
+                Action::NextColormap => {
+                    entity_state.heatmap.colormap = entity_state.heatmap.colormap.next()
+                }
+                Action::NextScaling => {
+                    entity_state.heatmap.scaling = entity_state.heatmap.scaling.next()
+                }
+                Action::ToggleHistogram => entity_state.values.toggle_histogram(),
+                Action::MoreBins => entity_state.histogram.more_bins(),
+                Action::FewerBins => entity_state.histogram.fewer_bins(),
+                Action::ToggleLogCounts => {
+                    entity_state.histogram.log_counts = !entity_state.histogram.log_counts
+                }
+                Action::ToggleHistogramScope => {
+                    entity_state.histogram.scope = entity_state.histogram.scope.next()
+                }
+                Action::SetIgnoredValues => {
+                    mode = Mode::IgnoreValues;
+                    input = entity_state.histogram.ignored_expression();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                }
+                Action::ComputeStatistics => {
+                    if let Some(Ok(EntityInfo::Dataset(dataset))) =
+                        position.clone().map(|position| file_info.entity(position))
                     {
-                        *mode = Mode::Search {
-                            search: String::default(),
-                        };
-                        contents_tree.state.search(Some(String::default()));
+                        entity_state.stats.start(dataset);
                     }
-                    (&mut Mode::Search { search: _ }, KeyCode::Esc, KeyModifiers::NONE) => {
+                }
+                Action::CancelStatistics => entity_state.stats.cancel(),
+                Action::SetXAxis => {
+                    mode = Mode::XAxis;
+                    input = entity_state.values.x_axis_path();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
+                }
+                Action::Confirm => match mode {
+                    Mode::Search => {
                         mode = Mode::default();
-                        contents_tree.state.search(None);
-                    }
-                    (
-                        &mut Mode::Search { ref mut search },
-                        KeyCode::Char(char),
-                        KeyModifiers::NONE,
-                    ) => {
-                        search.push(char);
-                        contents_tree.state.search(Some(search.clone()));
-                    }
-                    (
-                        &mut Mode::Search { ref mut search },
-                        KeyCode::Backspace,
-                        KeyModifiers::NONE,
-                    ) => {
-                        search.pop();
-                        contents_tree.state.search(Some(search.clone()));
+                        contents_tree.state.confirm_search();
+                        if contents_tree.state.position() != position {
+                            history.record(path.clone());
+                        }
+                    }
+                    Mode::Slice => {
+                        if entity_state.values.apply_slice().is_ok() {
+                            mode = Mode::default();
+                        }
                     }
+                    Mode::XAxis => {
+                        let x_axis = read_x_axis(&input, position.as_deref(), &file_info);
+                        if entity_state.values.set_x_axis(x_axis).is_ok() {
+                            mode = Mode::default();
+                        }
+                    }
+                    Mode::IgnoreValues => {
+                        if let Ok(ignored) = entity_state.values.set_ignored(parse_ignored(&input))
+                        {
+                            entity_state.histogram.ignored = ignored;
+                            mode = Mode::default();
+                        }
+                    }
+                    Mode::GoTo => match select_path(&input, &mut file_info, &mut contents_tree) {
+                        Ok(()) => {
+                            history.record(path.clone());
+                            mode = Mode::default();
+                            contents_tree.prompt = None;
+                        }
+                        Err(err) => {
+                            let mut prompt = PathPrompt::new(input.clone());
+                            prompt.error = Some(format!("{err:#}"));
+                            contents_tree.prompt = Some(prompt);
+                        }
+                    },
                     _ => {}
+                },
+                Action::Complete => {
+                    let mut prompt = PathPrompt::new(input.clone());
+                    match complete_path(&input, &mut file_info, &mut contents_tree) {
+                        Ok(completed) => {
+                            input = completed;
+                            prompt = PathPrompt::new(input.clone());
+                        }
+                        Err(err) => prompt.error = Some(format!("{err:#}")),
+                    }
+                    contents_tree.prompt = Some(prompt);
+                }
+                Action::Cancel => {
+                    match mode {
+                        Mode::Search => contents_tree.state.search(None),
+                        Mode::Slice => entity_state.values.edit_slice(None),
+                        Mode::XAxis => entity_state.values.edit_x_axis(None),
+                        Mode::IgnoreValues => entity_state.values.edit_ignored(None),
+                        Mode::GoTo => contents_tree.prompt = None,
+                        _ => {}
+                    }
+                    mode = Mode::default();
+                }
+                Action::DeleteChar => {
+                    input.pop();
+                    show_input(mode, &input, &mut contents_tree, &mut entity_state);
                 }
             }
         }
     }
+    graphics.show(terminal.backend_mut(), None)?;
     Ok(())
 }
@@ -0,0 +1,307 @@
+use crate::config::config_dir;
+use anyhow::{bail, Context};
+use regex::Regex;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+/// The paths jumped from and to, in the style of the vim jump list.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    jumps: Vec<String>,
+    /// One past the last jump, unless moving through the history.
+    current: usize,
+}
+
+impl History {
+    /// Records a jump from the path, if any, discarding any jumps which had been retraced.
+    pub fn record(&mut self, path: Option<String>) {
+        let Some(path) = path else {
+            return;
+        };
+        self.jumps.truncate(self.current);
+        if self.jumps.last() != Some(&path) {
+            self.jumps.push(path);
+        }
+        self.current = self.jumps.len();
+    }
+
+    /// Retraces the jump before the path, recording the path such that it can be returned to.
+    pub fn back(&mut self, path: &str) -> Option<String> {
+        if self.current == self.jumps.len() {
+            if self.jumps.last().map(String::as_str) != Some(path) {
+                self.jumps.push(path.to_string());
+            }
+            self.current = self.jumps.len() - 1;
+        }
+        self.current = self.current.checked_sub(1)?;
+        Some(self.jumps[self.current].clone())
+    }
+
+    /// Retraces the jump after the current path, if it was reached by moving back.
+    pub fn forward(&mut self) -> Option<String> {
+        if self.current + 1 >= self.jumps.len() {
+            return None;
+        }
+        self.current += 1;
+        Some(self.jumps[self.current].clone())
+    }
+}
+
+/// Paths marked with a character, in the style of vim marks, for files matching a pattern in
+/// which `*` matches any characters and `?` a single character.
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    file: Option<PathBuf>,
+    /// Comments, blank lines and invalid lines are kept such that they are saved again.
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone)]
+enum Line {
+    Bookmark(Bookmark),
+    Other(String),
+}
+
+#[derive(Debug, Clone)]
+struct Bookmark {
+    pattern: String,
+    matcher: Regex,
+    mark: char,
+    path: String,
+}
+
+impl Bookmark {
+    fn new(pattern: String, mark: char, path: String) -> Result<Self, anyhow::Error> {
+        let matcher = Regex::new(&format!(
+            "^{}$",
+            pattern
+                .split('*')
+                .map(|part| part
+                    .split('?')
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join("."))
+                .collect::<Vec<_>>()
+                .join(".*")
+        ))?;
+        Ok(Self {
+            pattern,
+            matcher,
+            mark,
+            path,
+        })
+    }
+}
+
+impl Bookmarks {
+    const FILE_NAME: &'static str = "bookmarks";
+
+    /// Reads the bookmarks, where each line holds a file pattern, a mark and a path separated by
+    /// tabs, along with an error for the first invalid line.
+    pub fn load() -> Result<(Self, Option<anyhow::Error>), anyhow::Error> {
+        let Some(file) = config_dir().map(|dir| dir.join(Self::FILE_NAME)) else {
+            return Ok((Self::default(), None));
+        };
+        let contents = match std::fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Could not read {}", file.display()))
+            }
+        };
+        Ok(Self::parse(file, &contents))
+    }
+
+    fn parse(file: PathBuf, contents: &str) -> (Self, Option<anyhow::Error>) {
+        let mut error = None;
+        let lines = contents
+            .lines()
+            .enumerate()
+            .map(|(number, line)| {
+                if line.trim().is_empty() || line.starts_with('#') {
+                    return Line::Other(line.to_string());
+                }
+                match Self::parse_line(line) {
+                    Ok(bookmark) => Line::Bookmark(bookmark),
+                    Err(err) => {
+                        error.get_or_insert(err.context(format!(
+                            "Skipped invalid bookmark on line {} of {}",
+                            number + 1,
+                            file.display()
+                        )));
+                        Line::Other(line.to_string())
+                    }
+                }
+            })
+            .collect();
+        let bookmarks = Self {
+            file: Some(file),
+            lines,
+        };
+        (bookmarks, error)
+    }
+
+    fn parse_line(line: &str) -> Result<Bookmark, anyhow::Error> {
+        let mut fields = line.split('\t');
+        let (Some(pattern), Some(mark), Some(path), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!("Expected a file pattern, a mark and a path, separated by tabs");
+        };
+        let mut chars = mark.chars();
+        let (Some(mark), None) = (chars.next(), chars.next()) else {
+            bail!("Expected a single character mark, found '{mark}'");
+        };
+        Bookmark::new(pattern.to_string(), mark, path.to_string())
+    }
+
+    /// The path marked in the file, where bookmarks added later take precedence.
+    pub fn get(&self, file: &Path, mark: char) -> Option<&str> {
+        let file = file.to_string_lossy();
+        self.bookmarks()
+            .rev()
+            .find(|bookmark| bookmark.mark == mark && bookmark.matcher.is_match(&file))
+            .map(|bookmark| bookmark.path.as_str())
+    }
+
+    fn bookmarks(&self) -> impl DoubleEndedIterator<Item = &Bookmark> + '_ {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Bookmark(bookmark) => Some(bookmark),
+            Line::Other(_) => None,
+        })
+    }
+
+    /// Marks the path in the file only, replacing any previous bookmark of it, and saves the
+    /// bookmarks.
+    pub fn set(&mut self, file: &Path, mark: char, path: String) -> Result<(), anyhow::Error> {
+        let pattern = file
+            .to_str()
+            .context("File path is not valid unicode")?
+            .replace(['*', '?'], "?");
+        for (name, field) in [("File path", pattern.as_str()), ("Path", path.as_str())] {
+            if field.contains(['\t', '\n', '\r']) {
+                bail!(
+                    "{name} '{}' contains a tab or line break",
+                    field.escape_debug()
+                );
+            }
+        }
+        if mark.is_control() {
+            bail!("Mark '{}' is a control character", mark.escape_debug());
+        }
+        self.lines.retain(|line| match line {
+            Line::Bookmark(bookmark) => !(bookmark.mark == mark && bookmark.pattern == pattern),
+            Line::Other(_) => true,
+        });
+        self.lines
+            .push(Line::Bookmark(Bookmark::new(pattern, mark, path)?));
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), anyhow::Error> {
+        let file = self
+            .file
+            .as_ref()
+            .context("Could not find a configuration directory")?;
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = self.lines.iter().fold(String::new(), |mut contents, line| {
+            let _ = match line {
+                Line::Bookmark(bookmark) => writeln!(
+                    contents,
+                    "{}\t{}\t{}",
+                    bookmark.pattern, bookmark.mark, bookmark.path
+                ),
+                Line::Other(line) => writeln!(contents, "{line}"),
+            };
+            contents
+        });
+        std::fs::write(file, contents)
+            .with_context(|| format!("Could not write {}", file.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmarks(contents: &str) -> Bookmarks {
+        let (bookmarks, error) = Bookmarks::parse(PathBuf::from("bookmarks"), contents);
+        assert!(error.is_none());
+        bookmarks
+    }
+
+    #[test]
+    fn matches_file_patterns() {
+        let bookmarks = bookmarks("/data/scan_*.nxs\td\t/entry/data\n/data/scan_?.h5\td\t/entry\n");
+        let get = |file: &str| bookmarks.get(Path::new(file), 'd');
+        assert_eq!(get("/data/scan_123.nxs"), Some("/entry/data"));
+        assert_eq!(get("/data/scan_.nxs"), Some("/entry/data"));
+        assert_eq!(get("/data/scan_1.h5"), Some("/entry"));
+        assert_eq!(get("/data/scan_12.h5"), None);
+        assert_eq!(get("/data/scan_1xnxs"), None);
+        assert_eq!(bookmarks.get(Path::new("/data/scan_1.h5"), 'e'), None);
+    }
+
+    #[test]
+    fn prefers_later_bookmarks() {
+        let bookmarks = bookmarks("*\ta\t/first\n/data/*\ta\t/second\n");
+        assert_eq!(
+            bookmarks.get(Path::new("/data/scan.nxs"), 'a'),
+            Some("/second")
+        );
+        assert_eq!(
+            bookmarks.get(Path::new("/other/scan.nxs"), 'a'),
+            Some("/first")
+        );
+    }
+
+    #[test]
+    fn skips_invalid_lines() {
+        let parse = |contents| Bookmarks::parse(PathBuf::from("bookmarks"), contents).1;
+        assert!(parse("# comment\n\n*\ta\t/entry\n").is_none());
+        assert!(parse("*\ta\n").is_some());
+        assert!(parse("*\ta\t/entry\textra\n").is_some());
+        assert!(parse("*\tab\t/entry\n").is_some());
+        let (bookmarks, _) = Bookmarks::parse(
+            PathBuf::from("bookmarks"),
+            "*\tab\t/invalid\n*\tb\t/entry\n",
+        );
+        assert_eq!(
+            bookmarks.get(Path::new("/data/scan.nxs"), 'b'),
+            Some("/entry")
+        );
+    }
+
+    #[test]
+    fn keeps_comments_when_saving() {
+        let file = std::env::temp_dir().join(format!("nexplore-bookmarks-{}", std::process::id()));
+        let (mut bookmarks, _) = Bookmarks::parse(
+            file.clone(),
+            "# Scans\n/data/scan.nxs\ta\t/old\n\n*\ta\t/entry\n",
+        );
+        bookmarks
+            .set(Path::new("/data/scan.nxs"), 'a', "/new".to_string())
+            .unwrap();
+        let contents = std::fs::read_to_string(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(
+            contents,
+            "# Scans\n\n*\ta\t/entry\n/data/scan.nxs\ta\t/new\n"
+        );
+    }
+
+    #[test]
+    fn rejects_paths_which_cannot_be_stored() {
+        let mut bookmarks = bookmarks("");
+        assert!(bookmarks
+            .set(Path::new("/data/scan.nxs"), 'a', "/entry\tdata".to_string())
+            .is_err());
+        assert!(bookmarks
+            .set(Path::new("/data/scan.nxs"), '\n', "/entry".to_string())
+            .is_err());
+    }
+}
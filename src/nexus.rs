@@ -0,0 +1,277 @@
+use crate::h5file::{AttributeInfo, FileInfo, GroupInfo};
+use anyhow::{bail, Context};
+
+/// The most `default` attributes followed before reporting a cycle.
+const MAX_DEFAULT_DEPTH: usize = 8;
+
+/// The data plotted by default, found by following the `default` attributes of a NeXus file.
+#[derive(Debug, Clone)]
+pub struct DefaultPlot {
+    pub signal: String,
+    /// The path of the axis along each dimension of the signal, if any.
+    pub axes: Vec<Option<String>>,
+}
+
+/// The label and coordinates of an axis.
+pub type Axis = (String, Vec<f64>);
+
+/// Follows `/@default` to an `NXentry`, its `@default` to an `NXdata` group, and its `@signal`
+/// and `@axes` to the datasets to plot, falling back to the conventions of older files.
+pub fn default_plot(file_info: &FileInfo) -> Result<DefaultPlot, anyhow::Error> {
+    let mut path = String::new();
+    let mut group = file_info.group("/")?;
+    for _ in 0..MAX_DEFAULT_DEPTH {
+        if group.class.as_deref() == Some("NXdata") {
+            return plot_of(file_info, &path, &group);
+        }
+        let name = match group.attribute("default") {
+            Some(name) => name.to_string(),
+            None => {
+                let class = if path.is_empty() { "NXentry" } else { "NXdata" };
+                first_of_class(&group, class)?.with_context(|| {
+                    format!(
+                        "{} has no @default attribute and no {class} group",
+                        display(&path)
+                    )
+                })?
+            }
+        };
+        let child_path = format!("{path}/{name}");
+        group = file_info.group(&child_path).with_context(|| {
+            format!(
+                "{}@default names '{name}', which is not a group",
+                display(&path)
+            )
+        })?;
+        path = child_path;
+    }
+    bail!(
+        "The @default attributes from {} form a cycle",
+        display(&path)
+    )
+}
+
+/// Reads the coordinates of each axis of the plot, along with the first error.
+pub fn read_axes(
+    file_info: &FileInfo,
+    plot: &DefaultPlot,
+) -> (Vec<Option<Axis>>, Option<anyhow::Error>) {
+    let mut error = None;
+    let axes = plot
+        .axes
+        .iter()
+        .map(|path| {
+            let path = path.as_ref()?;
+            match read_axis(file_info, path) {
+                Ok(axis) => Some(axis),
+                Err(err) => {
+                    error.get_or_insert(err.context(format!("Could not read the axis {path}")));
+                    None
+                }
+            }
+        })
+        .collect();
+    (axes, error)
+}
+
+fn read_axis(file_info: &FileInfo, path: &str) -> Result<Axis, anyhow::Error> {
+    let values = file_info.read_axis(path)?;
+    let dataset = file_info.dataset(path)?;
+    let label = match dataset.attribute("units") {
+        Some(units) => format!("{} ({units})", dataset.name),
+        None => dataset.name.clone(),
+    };
+    Ok((label, values))
+}
+
+fn plot_of(
+    file_info: &FileInfo,
+    path: &str,
+    group: &GroupInfo,
+) -> Result<DefaultPlot, anyhow::Error> {
+    let signal = match group.attribute("signal") {
+        Some(signal) => signal.to_string(),
+        None => group
+            .datasets()?
+            .iter()
+            .find(|dataset| dataset.is_signal())
+            .map(|dataset| dataset.name.clone())
+            .with_context(|| {
+                format!(
+                    "{} has no @signal attribute and no dataset with signal=1",
+                    display(path)
+                )
+            })?,
+    };
+    let signal_path = format!("{path}/{signal}");
+    let dataset = file_info.dataset(&signal_path).with_context(|| {
+        format!(
+            "{}@signal names '{signal}', which is not a dataset",
+            display(path)
+        )
+    })?;
+    let names = match group
+        .attribute_info("axes")
+        .or(dataset.attribute_info("axes"))
+    {
+        Some(axes) => parse_axes(axes),
+        None => {
+            let datasets = group.datasets()?;
+            let numbers = datasets
+                .iter()
+                .filter_map(|dataset| Some((dataset.name.as_str(), dataset.attribute("axis")?)))
+                .collect::<Vec<_>>();
+            numbered_axes(&numbers, dataset.shape.len())
+        }
+    };
+    let axes = assign_axes(&names, dataset.shape.len(), |name| {
+        group.attribute_info(&format!("{name}_indices"))
+    })
+    .into_iter()
+    .map(|name| name.map(|name| format!("{path}/{name}")))
+    .collect();
+    Ok(DefaultPlot {
+        signal: signal_path,
+        axes,
+    })
+}
+
+/// Older files give the axes as a single string separated by colons or commas.
+fn parse_axes(axes: &AttributeInfo) -> Vec<String> {
+    match axes.elements.as_slice() {
+        [names] if axes.shape.is_empty() => names
+            .split([',', ':'])
+            .map(|name| name.trim().to_string())
+            .collect(),
+        names => names.iter().map(|name| name.trim().to_string()).collect(),
+    }
+}
+
+/// Places each axis on the dimension given by its `AXISNAME_indices` attribute, if any, and
+/// otherwise by its position. Axes spanning several dimensions are not placed.
+fn assign_axes<'a>(
+    names: &'a [String],
+    ndim: usize,
+    indices: impl Fn(&str) -> Option<&'a AttributeInfo>,
+) -> Vec<Option<&'a str>> {
+    let mut axes = vec![None; ndim];
+    for (position, name) in names.iter().enumerate() {
+        if name.is_empty() || name == "." {
+            continue;
+        }
+        let dims = indices(name)
+            .map(|indices| {
+                indices
+                    .elements
+                    .iter()
+                    .map(|index| index.trim().parse::<usize>().ok())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .unwrap_or(Some(vec![position]));
+        if let Some(&[dim]) = dims.as_deref() {
+            if let Some(axis) = axes.get_mut(dim) {
+                *axis = Some(name.as_str());
+            }
+        }
+    }
+    axes
+}
+
+/// The axes of older files, given by datasets whose `axis` attribute numbers the dimension
+/// from one.
+fn numbered_axes(numbers: &[(&str, &str)], ndim: usize) -> Vec<String> {
+    (1..=ndim)
+        .map(|dim| {
+            numbers
+                .iter()
+                .find(|(_, number)| number.trim() == dim.to_string())
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn first_of_class(group: &GroupInfo, class: &str) -> Result<Option<String>, anyhow::Error> {
+    Ok(group
+        .subgroups()?
+        .into_iter()
+        .find(|subgroup| subgroup.class.as_deref() == Some(class))
+        .map(|subgroup| subgroup.name))
+}
+
+fn display(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(shape: &[usize], elements: &[&str]) -> AttributeInfo {
+        AttributeInfo {
+            name: String::new(),
+            dtype: String::new(),
+            shape: shape.to_vec(),
+            value: Ok(elements.join(", ")),
+            elements: elements.iter().map(|element| element.to_string()).collect(),
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_scalar_axes() {
+        assert_eq!(parse_axes(&attribute(&[], &["x:y"])), names(&["x", "y"]));
+        assert_eq!(parse_axes(&attribute(&[], &["x, ."])), names(&["x", "."]));
+    }
+
+    #[test]
+    fn keeps_the_names_of_array_axes() {
+        let axes = attribute(&[2], &["energy, keV", " y "]);
+        assert_eq!(parse_axes(&axes), names(&["energy, keV", "y"]));
+        assert_eq!(parse_axes(&attribute(&[1], &["x:y"])), names(&["x:y"]));
+    }
+
+    #[test]
+    fn assigns_axes_by_position() {
+        let names = names(&[".", "x", "y"]);
+        assert_eq!(
+            assign_axes(&names, 3, |_| None),
+            vec![None, Some("x"), Some("y")]
+        );
+        assert_eq!(assign_axes(&names, 2, |_| None), vec![None, Some("x")]);
+    }
+
+    #[test]
+    fn assigns_axes_by_indices() {
+        let names = names(&["time", "x"]);
+        let time_indices = attribute(&[1], &["1"]);
+        let x_indices = attribute(&[], &["0"]);
+        let axes = assign_axes(&names, 2, |name| match name {
+            "time" => Some(&time_indices),
+            "x" => Some(&x_indices),
+            _ => None,
+        });
+        assert_eq!(axes, vec![Some("x"), Some("time")]);
+    }
+
+    #[test]
+    fn skips_axes_spanning_several_dimensions() {
+        let names = names(&["grid", "y"]);
+        let grid_indices = attribute(&[2], &["0", "1"]);
+        let axes = assign_axes(&names, 2, |name| (name == "grid").then_some(&grid_indices));
+        assert_eq!(axes, vec![None, Some("y")]);
+    }
+
+    #[test]
+    fn numbers_axes_from_one() {
+        let numbers = [("y", "2"), ("x", "1"), ("z", "4")];
+        assert_eq!(numbered_axes(&numbers, 3), names(&["x", "y", ""]));
+    }
+}
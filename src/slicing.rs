@@ -0,0 +1,404 @@
+use anyhow::{bail, Context};
+use ndarray::SliceInfoElem;
+use std::{fmt::Display, ops::Range};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimSlice {
+    Index(usize),
+    Range {
+        start: usize,
+        end: usize,
+        step: usize,
+    },
+}
+
+impl DimSlice {
+    fn full(length: usize) -> Self {
+        Self::Range {
+            start: 0,
+            end: length,
+            step: 1,
+        }
+    }
+
+    /// Parses a numpy style index or slice, such as `-1` or `10:20`.
+    fn parse(term: &str, length: usize) -> Result<Self, anyhow::Error> {
+        let resolve = |value: &str, default: usize| -> Result<usize, anyhow::Error> {
+            let value = value.trim();
+            if value.is_empty() {
+                return Ok(default);
+            }
+            let value = value.parse::<isize>()?;
+            Ok(if value < 0 {
+                length.saturating_sub(value.unsigned_abs())
+            } else {
+                (value as usize).min(length)
+            })
+        };
+        if term.contains(':') {
+            let parts = term.split(':').collect::<Vec<_>>();
+            if parts.len() > 3 {
+                bail!("Expected at most two colons");
+            }
+            let step = match parts.get(2).map(|step| step.trim()) {
+                None | Some("") => 1,
+                Some(step) => step
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&step| step > 0)
+                    .context("Step must be a positive integer")?,
+            };
+            let start = resolve(parts[0], 0)?;
+            let end = resolve(parts[1], length)?.max(start);
+            Ok(Self::Range { start, end, step })
+        } else {
+            let index = term.trim().parse::<isize>()?;
+            let resolved = if index < 0 {
+                length.checked_sub(index.unsigned_abs())
+            } else {
+                Some(index as usize).filter(|&index| index < length)
+            };
+            Ok(Self::Index(resolved.with_context(|| {
+                format!("Index {index} is out of bounds for dimension of length {length}")
+            })?))
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Index(_) => 1,
+            Self::Range { start, end, step } => (end - start).div_ceil(*step),
+        }
+    }
+
+    fn format(&self, length: usize) -> String {
+        match *self {
+            Self::Index(index) => index.to_string(),
+            Self::Range { start, end, step } => {
+                let start = if start == 0 {
+                    String::new()
+                } else {
+                    start.to_string()
+                };
+                let end = if end == length {
+                    String::new()
+                } else {
+                    end.to_string()
+                };
+                if step == 1 {
+                    format!("{start}:{end}")
+                } else {
+                    format!("{start}:{end}:{step}")
+                }
+            }
+        }
+    }
+}
+
+/// A hyperslab of a dataset in which at most two dimensions are displayed and all others are
+/// fixed at an index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetSlice {
+    shape: Vec<usize>,
+    dims: Vec<DimSlice>,
+    rows: Option<usize>,
+    columns: Option<usize>,
+}
+
+impl DatasetSlice {
+    /// Creates a slice which displays the trailing two dimensions of the dataset.
+    pub fn new(shape: &[usize]) -> Self {
+        let ndim = shape.len();
+        let dims = shape
+            .iter()
+            .enumerate()
+            .map(|(axis, &length)| {
+                if axis + 2 < ndim {
+                    DimSlice::Index(0)
+                } else {
+                    DimSlice::full(length)
+                }
+            })
+            .collect();
+        let (rows, columns) = match ndim {
+            0 => (None, None),
+            1 => (Some(0), None),
+            _ => (Some(ndim - 2), Some(ndim - 1)),
+        };
+        Self {
+            shape: shape.to_vec(),
+            dims,
+            rows,
+            columns,
+        }
+    }
+
+    /// Parses a numpy style slice expression, such as `[10, ..., 100:200]`, where an empty
+    /// expression gives the default slice.
+    pub fn parse(expression: &str, shape: &[usize]) -> Result<Self, anyhow::Error> {
+        let expression = expression.trim();
+        let expression = match expression.strip_prefix('[') {
+            Some(expression) => expression
+                .strip_suffix(']')
+                .context("Expected a closing bracket")?,
+            None => expression,
+        };
+        if expression.trim().is_empty() {
+            return Ok(Self::new(shape));
+        }
+        let terms = expression.split(',').map(str::trim).collect::<Vec<_>>();
+        let ellipses = terms.iter().filter(|&&term| term == "...").count();
+        if ellipses > 1 {
+            bail!("Expected at most one ellipsis");
+        }
+        let explicit = terms.len() - ellipses;
+        if explicit > shape.len() {
+            bail!(
+                "Found {explicit} indices for a dataset with {} dimensions",
+                shape.len()
+            );
+        }
+        let mut dims = Vec::with_capacity(shape.len());
+        for term in terms {
+            if term == "..." {
+                let fill = shape.len() - explicit;
+                dims.extend(
+                    shape[dims.len()..dims.len() + fill]
+                        .iter()
+                        .map(|&length| DimSlice::full(length)),
+                );
+            } else {
+                let length = shape[dims.len()];
+                dims.push(
+                    DimSlice::parse(term, length)
+                        .with_context(|| format!("Could not parse '{term}'"))?,
+                );
+            }
+        }
+        dims.extend(
+            shape[dims.len()..]
+                .iter()
+                .map(|&length| DimSlice::full(length)),
+        );
+        let displayed = dims
+            .iter()
+            .enumerate()
+            .filter(|(_, dim)| matches!(dim, DimSlice::Range { .. }))
+            .map(|(axis, _)| axis)
+            .collect::<Vec<_>>();
+        if displayed.len() > 2 {
+            bail!(
+                "At most two dimensions can be displayed, but {} were sliced",
+                displayed.len()
+            );
+        }
+        Ok(Self {
+            shape: shape.to_vec(),
+            dims,
+            rows: displayed.first().copied(),
+            columns: displayed.get(1).copied(),
+        })
+    }
+
+    pub fn ndim(&self) -> usize {
+        self.dims.len()
+    }
+
+    pub fn rows(&self) -> Option<usize> {
+        self.rows
+    }
+
+    pub fn columns(&self) -> Option<usize> {
+        self.columns
+    }
+
+    pub fn len(&self, axis: usize) -> usize {
+        self.dims[axis].len()
+    }
+
+    /// The dataset coordinate of the n-th selected element along an axis.
+    pub fn coordinate(&self, axis: usize, n: usize) -> usize {
+        match self.dims[axis] {
+            DimSlice::Index(index) => index,
+            DimSlice::Range { start, step, .. } => start + n * step,
+        }
+    }
+
+    /// Moves the index of a fixed axis by the given number of elements.
+    pub fn step(&mut self, axis: usize, delta: isize) {
+        if let Some(DimSlice::Index(index)) = self.dims.get_mut(axis) {
+            *index = index
+                .saturating_add_signed(delta)
+                .min(self.shape[axis].saturating_sub(1));
+        }
+    }
+
+    pub fn display_as_rows(&mut self, axis: usize) {
+        if axis >= self.ndim() || self.rows == Some(axis) {
+            return;
+        }
+        if self.columns == Some(axis) {
+            self.columns = self.rows;
+        } else if let Some(rows) = self.rows {
+            self.fix(rows);
+        }
+        self.show(axis);
+        self.rows = Some(axis);
+    }
+
+    pub fn display_as_columns(&mut self, axis: usize) {
+        if axis >= self.ndim() || self.columns == Some(axis) {
+            return;
+        }
+        if self.rows.is_none() {
+            return self.display_as_rows(axis);
+        }
+        if self.rows == Some(axis) {
+            self.rows = self.columns;
+            if self.rows.is_none() {
+                self.rows = Some(axis);
+                return;
+            }
+        } else if let Some(columns) = self.columns {
+            self.fix(columns);
+        }
+        self.show(axis);
+        self.columns = Some(axis);
+    }
+
+    fn fix(&mut self, axis: usize) {
+        if let DimSlice::Range { start, .. } = self.dims[axis] {
+            self.dims[axis] = DimSlice::Index(start.min(self.shape[axis].saturating_sub(1)));
+        }
+    }
+
+    fn show(&mut self, axis: usize) {
+        if let DimSlice::Index(_) = self.dims[axis] {
+            self.dims[axis] = DimSlice::full(self.shape[axis]);
+        }
+    }
+
+    /// Creates a selection which reads a window of the displayed rows and columns, given in
+    /// selected elements rather than dataset coordinates.
+    pub fn selection(&self, rows: Range<usize>, columns: Range<usize>) -> Vec<SliceInfoElem> {
+        self.strided_selection(rows, columns, (1, 1))
+    }
+
+    /// Creates a selection which reads every n-th element of a window.
+    pub fn strided_selection(
+        &self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+        (row_stride, column_stride): (usize, usize),
+    ) -> Vec<SliceInfoElem> {
+        self.dims
+            .iter()
+            .enumerate()
+            .map(|(axis, dim)| match *dim {
+                DimSlice::Index(index) => SliceInfoElem::Index(index as isize),
+                DimSlice::Range { start, end, step } => {
+                    let (window, stride) = if Some(axis) == self.rows {
+                        (&rows, row_stride)
+                    } else {
+                        (&columns, column_stride)
+                    };
+                    SliceInfoElem::Slice {
+                        start: (start + window.start * step) as isize,
+                        end: Some(end.min(start + window.end * step) as isize),
+                        step: (step * stride.max(1)) as isize,
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn terms(&self) -> Vec<String> {
+        self.dims
+            .iter()
+            .zip(&self.shape)
+            .map(|(dim, &length)| dim.format(length))
+            .collect()
+    }
+}
+
+impl Display for DatasetSlice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}]", self.terms().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: usize, end: usize, step: usize) -> DimSlice {
+        DimSlice::Range { start, end, step }
+    }
+
+    #[test]
+    fn parses_indices_and_ranges() {
+        let slice = DatasetSlice::parse("[2, 10:20, ::3]", &[5, 30, 10]).unwrap();
+        assert_eq!(
+            slice.dims,
+            vec![DimSlice::Index(2), range(10, 20, 1), range(0, 10, 3)]
+        );
+        assert_eq!((slice.rows(), slice.columns()), (Some(1), Some(2)));
+        assert_eq!(slice.len(2), 4);
+        assert_eq!(slice.to_string(), "[2, 10:20, ::3]");
+    }
+
+    #[test]
+    fn resolves_negative_and_out_of_range_bounds() {
+        let slice = DatasetSlice::parse("-1, -5:100", &[4, 10]).unwrap();
+        assert_eq!(slice.dims, vec![DimSlice::Index(3), range(5, 10, 1)]);
+        let slice = DatasetSlice::parse("8:2", &[10]).unwrap();
+        assert_eq!(slice.dims, vec![range(8, 8, 1)]);
+        assert_eq!(slice.len(0), 0);
+    }
+
+    #[test]
+    fn fills_omitted_and_elided_dimensions() {
+        let slice = DatasetSlice::parse("1", &[3, 4, 5]).unwrap();
+        assert_eq!(
+            slice.dims,
+            vec![DimSlice::Index(1), range(0, 4, 1), range(0, 5, 1)]
+        );
+        let slice = DatasetSlice::parse("..., 0", &[3, 4, 5]).unwrap();
+        assert_eq!(
+            slice.dims,
+            vec![range(0, 3, 1), range(0, 4, 1), DimSlice::Index(0)]
+        );
+    }
+
+    #[test]
+    fn resets_to_the_default_slice_when_empty() {
+        for shape in [&[3, 4][..], &[3, 4, 5], &[2, 3, 4, 5]] {
+            for expression in ["", " ", "[]", "[ ]"] {
+                assert_eq!(
+                    DatasetSlice::parse(expression, shape).unwrap(),
+                    DatasetSlice::new(shape)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        let shape = [3, 4, 5];
+        for expression in [
+            "5",
+            "::0",
+            "1, 2, 3, 4",
+            "..., ...",
+            ":, :, :",
+            "[1",
+            "a",
+            "1:2:3:4",
+        ] {
+            assert!(
+                DatasetSlice::parse(expression, &shape).is_err(),
+                "{expression} should be rejected"
+            );
+        }
+    }
+}
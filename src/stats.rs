@@ -0,0 +1,460 @@
+use crate::h5file::{DatasetInfo, DatasetLayoutInfo};
+use anyhow::bail;
+use ndarray::SliceInfoElem;
+use std::collections::HashMap;
+
+/// The number of values read between events.
+const CHUNK_VALUES: usize = 1 << 21;
+
+/// Summary statistics of a dataset, where the extremes and moments are of the finite values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statistics {
+    pub count: u64,
+    pub finite: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// The sum of squared differences from the mean.
+    m2: f64,
+    pub nan: u64,
+    pub infinite: u64,
+    pub zeros: u64,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            finite: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            m2: 0.0,
+            nan: 0,
+            infinite: 0,
+            zeros: 0,
+        }
+    }
+}
+
+impl Statistics {
+    /// Updates the mean and variance with Welford's algorithm, which stays accurate over many
+    /// values.
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        if value.is_nan() {
+            self.nan += 1;
+            return;
+        }
+        if value.is_infinite() {
+            self.infinite += 1;
+            return;
+        }
+        if value == 0.0 {
+            self.zeros += 1;
+        }
+        self.finite += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.finite as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    pub fn std(&self) -> f64 {
+        if self.finite == 0 {
+            f64::NAN
+        } else {
+            (self.m2 / self.finite as f64).sqrt()
+        }
+    }
+}
+
+/// Splits a dataset into blocks of at most [`CHUNK_VALUES`] values, aligned to its chunks such
+/// that each chunk is decompressed once.
+#[derive(Debug, Clone)]
+struct Blocks {
+    shape: Vec<usize>,
+    extent: Vec<usize>,
+}
+
+impl Blocks {
+    fn new(shape: &[usize], chunk_shape: Option<&[usize]>) -> Self {
+        let chunk_shape = chunk_shape.map_or_else(|| vec![1; shape.len()], <[usize]>::to_vec);
+        let mut extent = chunk_shape
+            .iter()
+            .zip(shape)
+            .map(|(&chunk, &length)| chunk.clamp(1, length.max(1)))
+            .collect::<Vec<_>>();
+        // Blocks grow from the last axis, by whole chunks, until they no longer span an axis.
+        for axis in (0..shape.len()).rev() {
+            let others = extent
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != axis)
+                .map(|(_, &extent)| extent)
+                .product::<usize>();
+            let chunk = chunk_shape[axis].max(1);
+            let fit = CHUNK_VALUES / others.max(1) / chunk * chunk;
+            extent[axis] = fit.clamp(extent[axis], shape[axis].max(1));
+            if extent[axis] < shape[axis] {
+                break;
+            }
+        }
+        Self {
+            shape: shape.to_vec(),
+            extent,
+        }
+    }
+
+    fn counts(&self) -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator + '_ {
+        self.shape
+            .iter()
+            .zip(&self.extent)
+            .map(|(&length, &extent)| length.div_ceil(extent))
+    }
+
+    fn len(&self) -> usize {
+        if self.shape.contains(&0) {
+            return 0;
+        }
+        self.counts().product()
+    }
+
+    fn selection(&self, n: usize) -> Vec<SliceInfoElem> {
+        let mut outer = n;
+        let mut selection = self
+            .counts()
+            .zip(&self.extent)
+            .zip(&self.shape)
+            .rev()
+            .map(|((count, &extent), &length)| {
+                let start = outer % count * extent;
+                outer /= count;
+                SliceInfoElem::Slice {
+                    start: start as isize,
+                    end: Some((start + extent).min(length) as isize),
+                    step: 1,
+                }
+            })
+            .collect::<Vec<_>>();
+        selection.reverse();
+        selection
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsTask {
+    dataset: DatasetInfo,
+    blocks: Blocks,
+    next: usize,
+    statistics: Statistics,
+}
+
+impl StatsTask {
+    fn new(dataset: DatasetInfo) -> Result<Self, anyhow::Error> {
+        if !dataset.is_numeric() {
+            match &dataset.datatype.descriptor {
+                Ok(descriptor) => bail!("Cannot compute statistics of type {descriptor}"),
+                Err(err) => bail!("Cannot compute statistics of an unsupported type: {err}"),
+            }
+        }
+        let chunk_shape = match &dataset.layout_info {
+            DatasetLayoutInfo::Chunked { chunk_shape, .. } => Some(chunk_shape.as_slice()),
+            _ => None,
+        };
+        Ok(Self {
+            blocks: Blocks::new(&dataset.shape, chunk_shape),
+            dataset,
+            next: 0,
+            statistics: Statistics::default(),
+        })
+    }
+
+    fn step(&mut self) -> Result<bool, anyhow::Error> {
+        if self.next < self.blocks.len() {
+            let values = self
+                .dataset
+                .read_numbers(&self.blocks.selection(self.next))?;
+            for &value in values.iter() {
+                self.statistics.add(value);
+            }
+            self.next += 1;
+        }
+        Ok(self.next < self.blocks.len())
+    }
+
+    /// The fraction of blocks which have been read.
+    pub fn progress(&self) -> f64 {
+        match self.blocks.len() {
+            0 => 1.0,
+            blocks => self.next as f64 / blocks as f64,
+        }
+    }
+}
+
+/// The values of a dataset within a selection, read a block at a time along its first sliced
+/// axis.
+#[derive(Debug, Clone)]
+pub struct SampleTask {
+    dataset: DatasetInfo,
+    selection: Vec<SliceInfoElem>,
+    axis: Option<usize>,
+    step: usize,
+    len: usize,
+    next: usize,
+    values: Vec<f64>,
+}
+
+impl SampleTask {
+    pub fn new(dataset: DatasetInfo, selection: Vec<SliceInfoElem>) -> Self {
+        let counts = selection
+            .iter()
+            .zip(&dataset.shape)
+            .map(|(elem, &length)| selected_count(elem, length))
+            .collect::<Vec<_>>();
+        let axis = selection
+            .iter()
+            .position(|elem| matches!(elem, SliceInfoElem::Slice { .. }));
+        let (step, len) = match axis {
+            Some(axis) => {
+                let trailing = counts[axis + 1..].iter().product::<usize>();
+                let step = (CHUNK_VALUES / trailing.max(1)).max(1);
+                (step, counts[axis].div_ceil(step))
+            }
+            None => (1, 1),
+        };
+        Self {
+            dataset,
+            selection,
+            axis,
+            step,
+            len: if counts.contains(&0) { 0 } else { len },
+            next: 0,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn reads(&self, id: i64, selection: &[SliceInfoElem]) -> bool {
+        self.dataset.id == id && self.selection == selection
+    }
+
+    fn block(&self, n: usize) -> Vec<SliceInfoElem> {
+        let mut selection = self.selection.clone();
+        if let Some(axis) = self.axis {
+            if let SliceInfoElem::Slice { start, end, step } = selection[axis] {
+                let end = end.unwrap_or(self.dataset.shape[axis] as isize);
+                let block_start = start + (n * self.step) as isize * step;
+                selection[axis] = SliceInfoElem::Slice {
+                    start: block_start,
+                    end: Some(end.min(block_start + self.step as isize * step)),
+                    step,
+                };
+            }
+        }
+        selection
+    }
+
+    /// Reads the next block, returning whether any blocks remain.
+    pub fn step(&mut self) -> Result<bool, anyhow::Error> {
+        if self.next < self.len {
+            let values = self.dataset.read_numbers(&self.block(self.next))?;
+            self.values.extend(values.iter());
+            self.next += 1;
+        }
+        Ok(self.next < self.len)
+    }
+
+    /// The fraction of blocks which have been read.
+    pub fn progress(&self) -> f64 {
+        match self.len {
+            0 => 1.0,
+            blocks => self.next as f64 / blocks as f64,
+        }
+    }
+
+    pub fn dataset_id(&self) -> i64 {
+        self.dataset.id
+    }
+
+    pub fn selection(&self) -> &[SliceInfoElem] {
+        &self.selection
+    }
+
+    pub fn into_values(self) -> Vec<f64> {
+        self.values
+    }
+}
+
+fn selected_count(elem: &SliceInfoElem, length: usize) -> usize {
+    match *elem {
+        SliceInfoElem::Slice { start, end, step } => {
+            let end = end.map_or(length, |end| end.max(0) as usize).min(length);
+            end.saturating_sub(start.max(0) as usize)
+                .div_ceil(step.max(1) as usize)
+        }
+        _ => 1,
+    }
+}
+
+/// The statistics computed for each dataset, by ID.
+#[derive(Debug, Clone, Default)]
+pub struct StatsState {
+    task: Option<StatsTask>,
+    results: HashMap<i64, Result<Statistics, String>>,
+}
+
+impl StatsState {
+    /// Starts computing the statistics of the dataset, cancelling any other computation.
+    pub fn start(&mut self, dataset: DatasetInfo) {
+        if self.results.contains_key(&dataset.id) || self.computing(dataset.id).is_some() {
+            return;
+        }
+        let id = dataset.id;
+        match StatsTask::new(dataset) {
+            Ok(task) => self.task = Some(task),
+            Err(err) => {
+                self.results.insert(id, Err(format!("{err:#}")));
+            }
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.task = None;
+    }
+
+    pub fn is_computing(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// The progress of the statistics of the dataset, if they are being computed.
+    pub fn computing(&self, id: i64) -> Option<f64> {
+        self.task
+            .as_ref()
+            .filter(|task| task.dataset.id == id)
+            .map(StatsTask::progress)
+    }
+
+    pub fn result(&self, id: i64) -> Option<&Result<Statistics, String>> {
+        self.results.get(&id)
+    }
+
+    /// Reads the next block of the statistics being computed, returning whether any remain.
+    pub fn step(&mut self) -> bool {
+        let Some(task) = self.task.as_mut() else {
+            return false;
+        };
+        let result = match task.step() {
+            Ok(true) => return true,
+            Ok(false) => Ok(task.statistics),
+            Err(err) => Err(format!("{err:#}")),
+        };
+        self.results.insert(task.dataset.id, result);
+        self.task = None;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(blocks: &Blocks, n: usize) -> Vec<(isize, isize)> {
+        blocks
+            .selection(n)
+            .into_iter()
+            .map(|elem| match elem {
+                SliceInfoElem::Slice {
+                    start,
+                    end: Some(end),
+                    ..
+                } => (start, end),
+                elem => panic!("Unexpected selection {elem:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn contiguous_blocks_span_trailing_axes() {
+        let blocks = Blocks::new(&[3, 4096, 1024], None);
+        assert_eq!(blocks.extent, vec![1, 2048, 1024]);
+        assert_eq!(blocks.len(), 6);
+        assert_eq!(ranges(&blocks, 3), vec![(1, 2), (2048, 4096), (0, 1024)]);
+    }
+
+    #[test]
+    fn chunked_blocks_align_to_chunks() {
+        let blocks = Blocks::new(&[10, 3000, 1000], Some(&[4, 100, 1000]));
+        assert_eq!(blocks.extent, vec![4, 500, 1000]);
+        assert_eq!(blocks.len(), 18);
+        assert_eq!(ranges(&blocks, 17), vec![(8, 10), (2500, 3000), (0, 1000)]);
+    }
+
+    #[test]
+    fn chunks_larger_than_a_block_are_read_whole() {
+        let blocks = Blocks::new(&[8192, 8192], Some(&[4096, 4096]));
+        assert_eq!(blocks.extent, vec![4096, 4096]);
+        assert_eq!(blocks.len(), 4);
+    }
+
+    #[test]
+    fn selected_counts_follow_steps() {
+        let slice = |start, end, step| SliceInfoElem::Slice { start, end, step };
+        assert_eq!(selected_count(&slice(0, None, 3), 10), 4);
+        assert_eq!(selected_count(&slice(2, Some(8), 2), 10), 3);
+        assert_eq!(selected_count(&slice(0, Some(20), 1), 10), 10);
+        assert_eq!(selected_count(&SliceInfoElem::Index(4), 10), 1);
+    }
+
+    fn statistics(values: impl IntoIterator<Item = f64>) -> Statistics {
+        let mut statistics = Statistics::default();
+        for value in values {
+            statistics.add(value);
+        }
+        statistics
+    }
+
+    #[test]
+    fn statistics_match_direct_calculation() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let statistics = statistics(values);
+        assert_eq!(statistics.count, 8);
+        assert_eq!((statistics.min, statistics.max), (2.0, 9.0));
+        assert!((statistics.mean - 5.0).abs() < 1e-12);
+        assert!((statistics.std() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn statistics_remain_accurate_with_large_offsets() {
+        let offset = 1e9;
+        let statistics = statistics((0..1_000_000).map(|idx| offset + (idx % 2) as f64));
+        assert!((statistics.mean - (offset + 0.5)).abs() < 1e-6);
+        assert!((statistics.std() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn statistics_count_special_values_separately() {
+        let statistics = statistics([f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0, -0.0, 3.0]);
+        assert_eq!(statistics.count, 6);
+        assert_eq!(statistics.finite, 3);
+        assert_eq!(statistics.nan, 1);
+        assert_eq!(statistics.infinite, 2);
+        assert_eq!(statistics.zeros, 2);
+        assert_eq!((statistics.min, statistics.max), (0.0, 3.0));
+        assert!((statistics.mean - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn statistics_of_no_finite_values_have_no_spread() {
+        assert!(Statistics::default().std().is_nan());
+        assert!(statistics([f64::NAN]).std().is_nan());
+        assert_eq!(statistics([1.5]).std(), 0.0);
+    }
+
+    #[test]
+    fn small_datasets_are_one_block() {
+        assert_eq!(Blocks::new(&[5, 7], Some(&[2, 2])).len(), 1);
+        assert_eq!(Blocks::new(&[], None).len(), 1);
+        assert_eq!(Blocks::new(&[0, 7], None).len(), 0);
+    }
+}
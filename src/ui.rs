@@ -1,14 +1,30 @@
 use crate::{
-    h5file::{DatasetInfo, DatasetLayoutInfo, EntityInfo, GroupInfo},
-    widgets::tree::{Tree, TreeItem, TreeState},
+    bindings::{Binding, Mode},
+    config::theme,
+    graphics::Placement,
+    h5file::{
+        AttributeInfo, BrokenLinkInfo, DatasetInfo, DatasetLayoutInfo, DatasetValues, DatatypeInfo,
+        DuplicateInfo, EntityInfo, GroupInfo, LinkKind,
+    },
+    nexus::Axis,
+    slicing::DatasetSlice,
+    stats::{SampleTask, StatsState},
+    widgets::{
+        heatmap::{image_size, Heatmap, HeatmapOptions, Normalization, Scaling},
+        histogram::{Histogram, HistogramChart, HistogramOptions, HistogramScope},
+        plot::{decimate, format_number, LinePlot, PlotWindow},
+        tree::{Tree, TreeItem, TreeState},
+    },
 };
+use hdf5::types::TypeDescriptor;
 use humansize::{format_size, ToF64, Unsigned, BINARY};
+use ndarray::{ArrayD, Ix2, SliceInfoElem};
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::Text,
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Widget},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, StatefulWidget, Table, Widget, Wrap},
     Frame,
 };
 
@@ -17,44 +33,195 @@ pub struct Screen {
     frame_layout: Layout,
     header_layout: Layout,
     data_layout: Layout,
+    contents_area: Rect,
+    entity_area: Rect,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Contents,
+    Entity,
+    Divider,
+}
+
+const MIN_PANEL_WIDTH: u16 = 10;
+const PROGRESS_WIDTH: usize = 20;
+
 impl Default for Screen {
     fn default() -> Self {
         Self {
             frame_layout: Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(3), Constraint::Ratio(1, 1)]),
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Ratio(1, 1),
+                    Constraint::Length(1),
+                ]),
             header_layout: Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Ratio(4, 5), Constraint::Ratio(1, 5)]),
             data_layout: Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Ratio(2, 5), Constraint::Ratio(3, 5)]),
+            contents_area: Rect::default(),
+            entity_area: Rect::default(),
         }
     }
 }
 
 impl Screen {
     pub fn render(
-        &self,
+        &mut self,
         frame: &mut Frame,
         file_name: &FileName,
         file_size: &FileSize,
         contents_tree: &mut ContentsTree,
-        entity_info: impl Widget,
+        entity_info: Option<EntityInfo>,
+        entity_state: &mut EntityState,
     ) {
         let vertical_chunks = self.frame_layout.split(frame.area());
         let header_chunks = self.header_layout.split(vertical_chunks[0]);
         frame.render_widget(file_name.0.clone(), header_chunks[0]);
         frame.render_widget(file_size.0.clone(), header_chunks[1]);
         let data_chunks = self.data_layout.split(vertical_chunks[1]);
+        self.contents_area = data_chunks[0];
+        self.entity_area = data_chunks[1];
         frame.render_stateful_widget(
             contents_tree.widget.clone(),
             data_chunks[0],
             &mut contents_tree.state,
         );
-        frame.render_widget(entity_info, data_chunks[1]);
+        let tree_area = data_chunks[0];
+        let prompt_area = Rect::new(
+            tree_area.left() + 1,
+            tree_area.bottom().saturating_sub(1),
+            tree_area.width.saturating_sub(2),
+            1,
+        );
+        if let Some(prompt) = contents_tree.prompt.as_ref() {
+            frame.render_widget(prompt, prompt_area);
+        } else if let Some(message) = contents_tree.message.as_ref() {
+            frame.render_widget(
+                Paragraph::new(message.clone()).style(Style::new().fg(Color::Red)),
+                prompt_area,
+            );
+        }
+        match entity_info {
+            Some(entity_info) => {
+                frame.render_stateful_widget(entity_info, data_chunks[1], entity_state)
+            }
+            None => frame.render_widget(Block::default().borders(Borders::ALL), data_chunks[1]),
+        }
+    }
+
+    /// Renders the status line, along with every binding of the mode if help is shown.
+    pub fn render_bindings(
+        &self,
+        frame: &mut Frame,
+        mode: Mode,
+        bindings: &[&Binding],
+        show_help: bool,
+    ) {
+        let vertical_chunks = self.frame_layout.split(frame.area());
+        let status = std::iter::once(Span::styled(
+            format!(" {mode} "),
+            Style::new()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .chain(
+            bindings
+                .iter()
+                .filter(|binding| binding.status)
+                .flat_map(|binding| {
+                    [
+                        Span::styled(
+                            format!("  {}", binding.primary_key_name()),
+                            Style::new().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(format!(" {}", binding.description)),
+                    ]
+                }),
+        )
+        .collect::<Vec<_>>();
+        frame.render_widget(Line::from(status), vertical_chunks[2]);
+
+        if !show_help {
+            return;
+        }
+        let input = if mode.accepts_text() {
+            Some("Enter Text")
+        } else if mode.accepts_mark() {
+            Some("Choose Mark")
+        } else {
+            None
+        };
+        let rows = bindings
+            .iter()
+            .map(|binding| (binding.key_names(), binding.full_description()))
+            .chain(input.map(|input| ("Characters".to_string(), input.to_string())))
+            .collect::<Vec<_>>();
+        let keys_width = rows
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or_default() as u16;
+        let area = frame.area();
+        let width = area.width.min(80);
+        let height = area.height.min(rows.len() as u16 + 2);
+        let popup_area = Rect::new(
+            area.left() + (area.width - width) / 2,
+            area.top() + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let table = Table::new(
+            rows.into_iter().map(|(keys, description)| {
+                Row::new(vec![Cell::from(keys), Cell::from(description)])
+            }),
+            [Constraint::Length(keys_width), Constraint::Min(0)],
+        )
+        .column_spacing(2)
+        .block(
+            Block::default()
+                .title(format!("Help: {mode}"))
+                .title_bottom("Press any key to close")
+                .borders(Borders::ALL),
+        );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(table, popup_area);
+    }
+
+    pub fn panel_at(&self, column: u16, row: u16) -> Option<Panel> {
+        let position = Position::new(column, row);
+        if (self.contents_area.top()..self.contents_area.bottom()).contains(&row)
+            && (self.contents_area.right().saturating_sub(1)..=self.entity_area.left())
+                .contains(&column)
+        {
+            Some(Panel::Divider)
+        } else if self.contents_area.contains(position) {
+            Some(Panel::Contents)
+        } else if self.entity_area.contains(position) {
+            Some(Panel::Entity)
+        } else {
+            None
+        }
+    }
+
+    pub fn resize(&mut self, column: u16) {
+        let total = self.contents_area.width + self.entity_area.width;
+        if total < 2 * MIN_PANEL_WIDTH {
+            return;
+        }
+        let width = (column.saturating_sub(self.contents_area.left()) + 1)
+            .clamp(MIN_PANEL_WIDTH, total - MIN_PANEL_WIDTH);
+        self.data_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Ratio(width as u32, total as u32),
+                Constraint::Ratio((total - width) as u32, total as u32),
+            ]);
     }
 }
 
@@ -86,74 +253,177 @@ impl FileSize<'_> {
 pub struct ContentsTree<'a> {
     pub widget: Tree<'a>,
     pub state: TreeState<'a>,
+    pub prompt: Option<PathPrompt>,
+    /// Shown at the bottom of the tree, such as errors.
+    pub message: Option<String>,
 }
 
 impl<'a> ContentsTree<'a> {
     pub fn new(items: Vec<TreeItem<'a>>) -> Self {
         Self {
-            widget: Tree::default().block(Block::default().title("Contents").borders(Borders::ALL)),
+            widget: Tree::default()
+                .block(Block::default().title("Contents").borders(Borders::ALL))
+                .selection_color(theme().selection)
+                .match_style(
+                    Style::new()
+                        .fg(theme().search_match)
+                        .add_modifier(Modifier::BOLD),
+                ),
             state: TreeState::new(items),
+            prompt: None,
+            message: None,
         }
     }
 }
 
-impl Widget for EntityInfo {
+#[derive(Debug, Clone)]
+pub struct PathPrompt {
+    pub input: String,
+    pub error: Option<String>,
+}
+
+impl PathPrompt {
+    pub fn new(input: String) -> Self {
+        Self { input, error: None }
+    }
+}
+
+impl Widget for &PathPrompt {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let prompt = format!(":{}", self.input);
+        let cursor_area = Rect::new(
+            area.left() + prompt.chars().count() as u16,
+            area.top(),
+            1,
+            1,
+        )
+        .intersection(area);
+        let mut spans = vec![Span::raw(prompt)];
+        if let Some(error) = self.error.as_ref() {
+            spans.push(Span::styled(
+                format!("  {error}"),
+                Style::new().fg(Color::Red),
+            ));
+        }
+        buf.set_style(area, Style::reset());
+        Paragraph::new(Line::from(spans)).render(area, buf);
+        buf.set_style(cursor_area, Style::new().bg(Color::White));
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EntityState {
+    pub focused: bool,
+    pub values: ValuesState,
+    pub heatmap: HeatmapOptions,
+    pub histogram: HistogramOptions,
+    pub stats: StatsState,
+}
+
+impl StatefulWidget for EntityInfo {
+    type State = EntityState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         match self {
             EntityInfo::Group(group) => group.render(area, buf),
-            EntityInfo::Dataset(dataset) => dataset.render(area, buf),
+            EntityInfo::Dataset(dataset) => dataset.render(area, buf, state),
+            EntityInfo::Duplicate(duplicate) => duplicate.render(area, buf),
+            EntityInfo::Broken(broken) => broken.render(area, buf),
+            EntityInfo::Error(error) => error.render(area, buf),
         }
     }
 }
 
-const GROUP_COLOR: Color = Color::Blue;
-
 impl Widget for GroupInfo {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        Table::new(
-            [
-                Row::new(vec![Cell::from("ID"), Cell::from(self.id.to_string())]),
-                Row::new(vec![
-                    Cell::from("Link Type"),
-                    Cell::from(self.link_kind.to_string()),
-                ]),
-            ],
-            [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)],
-        )
-        .block(
-            Block::default()
-                .title(self.name.clone())
-                .border_style(Style::new().fg(GROUP_COLOR))
-                .borders(Borders::ALL),
-        )
-        .render(area, buf);
+        let rows = std::iter::once(Row::new(vec![
+            Cell::from("ID"),
+            Cell::from(self.id.to_string()),
+        ]))
+        .chain(link_rows(&self.link_kind))
+        .collect::<Vec<_>>();
+        let (title, color) = match self.class.as_deref() {
+            Some(class) => (
+                format!("{} : {class}", self.name),
+                theme().class_color(class),
+            ),
+            None => (self.name.clone(), theme().group),
+        };
+        let block = Block::default()
+            .title(title)
+            .border_style(Style::new().fg(color))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(rows.len() as u16 + 1),
+                Constraint::Min(0),
+            ])
+            .split(inner_area);
+        let table = Table::new(rows, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]);
+        Widget::render(table, chunks[0], buf);
+        Widget::render(attributes_table(&self.attributes), chunks[1], buf);
     }
 }
 
 impl From<GroupInfo> for TreeItem<'_> {
     fn from(group: GroupInfo) -> Self {
-        Self::new(
-            Text::raw(group.name),
-            GROUP_COLOR,
-            group.entities.into_iter().map(TreeItem::from).collect(),
-        )
+        let properties = attribute_properties(&group.attributes).collect();
+        let class = group.class.as_deref();
+        let color = class.map_or(theme().group, |class| theme().class_color(class));
+        let glyph = class
+            .and_then(|class| theme().class_glyph(class))
+            .map(str::to_string);
+        let annotation = class.map(|class| {
+            Span::styled(
+                format!(" : {class}"),
+                Style::new().add_modifier(Modifier::DIM),
+            )
+        });
+        let signal = group.signal().map(str::to_string);
+        let children = group
+            .entities
+            .into_iter()
+            .flatten()
+            .map(|entity| {
+                // Older files mark the signal with an attribute rather than naming it.
+                let is_signal = signal.as_deref() == Some(entity.name())
+                    || matches!(&entity, EntityInfo::Dataset(dataset) if dataset.is_signal());
+                TreeItem::from(entity).emphasized(is_signal)
+            })
+            .collect();
+        Self::new(Text::raw(group.name), color, children)
+            .expandable(true)
+            .properties(properties)
+            .glyph(glyph)
+            .annotation(annotation)
+            .emphasized(class == Some("NXdata"))
     }
 }
 
-const DATASET_COLOR: Color = Color::Green;
+impl StatefulWidget for DatasetInfo {
+    type State = EntityState;
 
-impl Widget for DatasetInfo {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let mut rows = vec![
-            Row::new(vec![Cell::from("ID"), Cell::from(self.id.to_string())]),
-            Row::new(vec![
-                Cell::from("Link Type"),
-                Cell::from(self.link_type.to_string()),
-            ]),
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let datatype_lines = datatype_lines(&self.datatype);
+        let datatype_height = datatype_lines.len() as u16;
+        let mut rows = vec![Row::new(vec![
+            Cell::from("ID"),
+            Cell::from(self.id.to_string()),
+        ])];
+        rows.extend(link_rows(&self.link_type));
+        rows.extend([
             Row::new(vec![
                 Cell::from("Shape"),
                 Cell::from(format!("{:?}", self.shape)),
             ]),
+            Row::new(vec![
+                Cell::from("Datatype"),
+                Cell::from(Text::from(datatype_lines)),
+            ])
+            .height(datatype_height),
             Row::new(vec![
                 Cell::from("Layout"),
                 Cell::from(match self.layout_info {
@@ -166,7 +436,7 @@ impl Widget for DatasetInfo {
                     DatasetLayoutInfo::Virtial {} => "Virtual",
                 }),
             ]),
-        ];
+        ]);
 
         match self.layout_info.clone() {
             DatasetLayoutInfo::Compact {} => {}
@@ -188,20 +458,1247 @@ impl Widget for DatasetInfo {
             }
             DatasetLayoutInfo::Virtial {} => {}
         }
+        rows.extend(statistics_rows(&state.stats, self.id));
+
+        let block = Block::default()
+            .title(self.name.clone())
+            .border_style(Style::new().fg(theme().dataset))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(rows.len() as u16 + datatype_height),
+                Constraint::Length(attributes_height(&self.attributes).min(inner_area.height / 2)),
+                Constraint::Min(0),
+            ])
+            .split(inner_area);
+        let table = Table::new(rows, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]);
+        Widget::render(table, chunks[0], buf);
+        Widget::render(attributes_table(&self.attributes), chunks[1], buf);
+        ValuesView::new(&self, state.focused, state.heatmap, &state.histogram).render(
+            chunks[2],
+            buf,
+            &mut state.values,
+        );
+    }
+}
+
+impl Widget for DuplicateInfo {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = link_rows(&self.link_kind).chain([
+            Row::new(vec![Cell::from("Shown At"), Cell::from(self.path.clone())]),
+            Row::new(vec![
+                Cell::from(""),
+                Cell::from("Press Enter to jump to it"),
+            ])
+            .style(Style::new().add_modifier(Modifier::DIM)),
+        ]);
+        let table = Table::new(rows, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).block(
+            Block::default()
+                .title(self.name.clone())
+                .border_style(Style::new().fg(theme().link))
+                .borders(Borders::ALL),
+        );
+        Widget::render(table, area, buf);
+    }
+}
+
+impl From<DuplicateInfo> for TreeItem<'_> {
+    fn from(duplicate: DuplicateInfo) -> Self {
+        Self::new(
+            Text::raw(format!(
+                "{} \u{21bb} already shown at {}",
+                duplicate.name, duplicate.path
+            )),
+            theme().link,
+            vec![],
+        )
+    }
+}
 
-        Table::new(rows, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+impl Widget for BrokenLinkInfo {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = link_rows(&self.link_kind).chain([Row::new(vec![
+            Cell::from("Error"),
+            Cell::from(self.error.clone()),
+        ])
+        .style(Style::new().fg(theme().broken_link))]);
+        let table = Table::new(rows, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).block(
+            Block::default()
+                .title(self.name.clone())
+                .border_style(Style::new().fg(theme().broken_link))
+                .borders(Borders::ALL),
+        );
+        Widget::render(table, area, buf);
+    }
+}
+
+impl From<BrokenLinkInfo> for TreeItem<'_> {
+    fn from(broken: BrokenLinkInfo) -> Self {
+        Self::new(Text::raw(broken.name), theme().broken_link, vec![])
+    }
+}
+
+impl Widget for ErrorInfo {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.error.clone())
+            .style(Style::new().fg(theme().error))
+            .wrap(Wrap { trim: false })
             .block(
                 Block::default()
                     .title(self.name.clone())
-                    .border_style(Style::new().fg(DATASET_COLOR))
+                    .border_style(Style::new().fg(theme().error))
                     .borders(Borders::ALL),
             )
             .render(area, buf);
     }
 }
 
+impl From<ErrorInfo> for TreeItem<'_> {
+    fn from(error: ErrorInfo) -> Self {
+        Self::new(Text::raw(error.name), theme().error, vec![])
+    }
+}
+
+fn link_rows(link_kind: &LinkKind) -> impl Iterator<Item = Row<'static>> {
+    let targets = match link_kind {
+        LinkKind::Hard => vec![],
+        LinkKind::Soft { target } => vec![("Link Target", target.clone())],
+        LinkKind::External { file, target } => vec![
+            ("Link Target File", file.clone()),
+            ("Link Target", target.clone()),
+        ],
+    };
+    std::iter::once(("Link Type", link_kind.to_string()))
+        .chain(targets)
+        .map(|(name, value)| Row::new(vec![Cell::from(name), Cell::from(value)]))
+}
+
+fn progress_bar(progress: f64) -> String {
+    let filled = (progress * PROGRESS_WIDTH as f64).round() as usize;
+    format!(
+        "{}{} {:.0}%",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_WIDTH - filled),
+        progress * 100.0
+    )
+}
+
+fn statistics_rows(stats: &StatsState, id: i64) -> Vec<Row<'static>> {
+    if let Some(progress) = stats.computing(id) {
+        return vec![Row::new(vec![
+            Cell::from("Statistics"),
+            Cell::from(progress_bar(progress)),
+        ])];
+    }
+    let statistics = match stats.result(id) {
+        None => return vec![],
+        Some(Err(err)) => {
+            return vec![Row::new(vec![
+                Cell::from("Statistics"),
+                Cell::from(err.clone()).style(Style::new().fg(theme().error)),
+            ])]
+        }
+        Some(Ok(statistics)) => statistics,
+    };
+    // The extremes and moments are undefined if no values are finite.
+    let finite = |value: f64| {
+        if statistics.finite > 0 {
+            format_number(value)
+        } else {
+            "-".to_string()
+        }
+    };
+    [
+        ("Minimum", finite(statistics.min)),
+        ("Maximum", finite(statistics.max)),
+        ("Mean", finite(statistics.mean)),
+        ("Standard Deviation", finite(statistics.std())),
+        ("NaN Count", statistics.nan.to_string()),
+        ("Infinite Count", statistics.infinite.to_string()),
+        ("Zero Count", statistics.zeros.to_string()),
+    ]
+    .into_iter()
+    .map(|(name, value)| Row::new(vec![Cell::from(name), Cell::from(value)]))
+    .collect()
+}
+
+struct TypeNode {
+    label: String,
+    children: Vec<TypeNode>,
+}
+
+impl TypeNode {
+    fn leaf(label: String) -> Self {
+        Self {
+            label,
+            children: Vec::new(),
+        }
+    }
+
+    fn from_descriptor(descriptor: &TypeDescriptor) -> Self {
+        match descriptor {
+            TypeDescriptor::Integer(size) => {
+                Self::leaf(format!("Signed Integer ({} bytes)", *size as usize))
+            }
+            TypeDescriptor::Unsigned(size) => {
+                Self::leaf(format!("Unsigned Integer ({} bytes)", *size as usize))
+            }
+            TypeDescriptor::Float(size) => Self::leaf(format!("Float ({} bytes)", *size as usize)),
+            TypeDescriptor::Boolean => Self::leaf("Boolean".to_string()),
+            TypeDescriptor::Enum(enum_type) => Self {
+                label: format!(
+                    "Enum ({}, {} bytes)",
+                    if enum_type.signed {
+                        "Signed"
+                    } else {
+                        "Unsigned"
+                    },
+                    enum_type.size as usize
+                ),
+                children: enum_type
+                    .members
+                    .iter()
+                    .map(|member| {
+                        Self::leaf(if enum_type.signed {
+                            format!("{} = {}", member.name, member.value as i64)
+                        } else {
+                            format!("{} = {}", member.name, member.value)
+                        })
+                    })
+                    .collect(),
+            },
+            TypeDescriptor::Compound(compound_type) => Self {
+                label: format!("Compound ({} bytes)", compound_type.size),
+                children: compound_type
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let mut node = Self::from_descriptor(&field.ty);
+                        node.label = format!("{} @ {}: {}", field.name, field.offset, node.label);
+                        node
+                    })
+                    .collect(),
+            },
+            TypeDescriptor::FixedArray(descriptor, length) => Self {
+                label: format!("Array [{length}]"),
+                children: vec![Self::from_descriptor(descriptor)],
+            },
+            TypeDescriptor::FixedAscii(length) => {
+                Self::leaf(format!("Fixed Length String ({length} bytes, ASCII)"))
+            }
+            TypeDescriptor::FixedUnicode(length) => {
+                Self::leaf(format!("Fixed Length String ({length} bytes, UTF-8)"))
+            }
+            TypeDescriptor::VarLenArray(descriptor) => Self {
+                label: "Variable Length Sequence".to_string(),
+                children: vec![Self::from_descriptor(descriptor)],
+            },
+            TypeDescriptor::VarLenAscii => Self::leaf("Variable Length String (ASCII)".to_string()),
+            TypeDescriptor::VarLenUnicode => {
+                Self::leaf("Variable Length String (UTF-8)".to_string())
+            }
+            descriptor => Self::leaf(descriptor.to_string()),
+        }
+    }
+
+    fn flatten(&self, prefix: &str, lines: &mut Vec<Line<'static>>) {
+        for (idx, child) in self.children.iter().enumerate() {
+            let last = idx + 1 == self.children.len();
+            let (branch, indent) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{prefix}{branch}"),
+                    Style::new().add_modifier(Modifier::DIM),
+                ),
+                Span::raw(child.label.clone()),
+            ]));
+            child.flatten(&format!("{prefix}{indent}"), lines);
+        }
+    }
+}
+
+fn datatype_lines(datatype: &DatatypeInfo) -> Vec<Line<'static>> {
+    let mut root = match &datatype.descriptor {
+        Ok(descriptor) => TypeNode::from_descriptor(descriptor),
+        Err(err) => TypeNode::leaf(format!("Unsupported ({} bytes): {err}", datatype.size)),
+    };
+    if let Some(byte_order) = &datatype.byte_order {
+        root.label = format!("{}, {byte_order}", root.label);
+    }
+    if let Some(string_padding) = &datatype.string_padding {
+        root.label = format!("{}, {string_padding}", root.label);
+    }
+    let mut lines = vec![Line::raw(root.label.clone())];
+    root.flatten("", &mut lines);
+    lines
+}
+
+fn attributes_height(attributes: &[AttributeInfo]) -> u16 {
+    if attributes.is_empty() {
+        0
+    } else {
+        attributes.len() as u16 + 2
+    }
+}
+
+fn attributes_table(attributes: &[AttributeInfo]) -> Table<'_> {
+    let header = Row::new(["Attribute", "Type", "Shape", "Value"])
+        .style(Style::new().add_modifier(Modifier::BOLD));
+    let shapes = attributes
+        .iter()
+        .map(|attribute| {
+            if attribute.shape.is_empty() {
+                "Scalar".to_string()
+            } else {
+                format!("{:?}", attribute.shape)
+            }
+        })
+        .collect::<Vec<_>>();
+    let widths = [
+        column_width(
+            attributes.iter().map(|attribute| attribute.name.len()),
+            "Attribute",
+        ),
+        column_width(
+            attributes.iter().map(|attribute| attribute.dtype.len()),
+            "Type",
+        ),
+        column_width(shapes.iter().map(String::len), "Shape"),
+        Constraint::Min(0),
+    ];
+    let rows = attributes
+        .iter()
+        .zip(shapes)
+        .map(|(attribute, shape)| {
+            let value = match &attribute.value {
+                Ok(value) => Cell::from(value.as_str()),
+                Err(err) => Cell::from(err.as_str()).style(Style::new().fg(Color::Red)),
+            };
+            Row::new(vec![
+                Cell::from(attribute.name.as_str()),
+                Cell::from(attribute.dtype.as_str()),
+                Cell::from(shape),
+                value,
+            ])
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        Table::default()
+    } else {
+        Table::new(rows, widths).header(header)
+    }
+}
+
+fn column_width(lengths: impl Iterator<Item = usize>, header: &str) -> Constraint {
+    Constraint::Length(lengths.chain([header.len()]).max().unwrap_or_default() as u16)
+}
+
 impl From<DatasetInfo> for TreeItem<'_> {
     fn from(dataset: DatasetInfo) -> Self {
-        Self::new(Text::raw(dataset.name), DATASET_COLOR, vec![])
+        let size = dataset.shape.iter().product::<usize>() * dataset.datatype.size;
+        let properties = attribute_properties(&dataset.attributes)
+            .chain([
+                ("ndim".to_string(), dataset.shape.len().to_string()),
+                ("size".to_string(), size.to_string()),
+            ])
+            .chain(
+                dataset
+                    .datatype
+                    .descriptor
+                    .iter()
+                    .flat_map(dtype_names)
+                    .map(|name| ("dtype".to_string(), name)),
+            )
+            .collect();
+        Self::new(Text::raw(dataset.name), theme().dataset, vec![]).properties(properties)
+    }
+}
+
+/// Each attribute can be found by its name, or its name and value, and the NeXus class by its
+/// name alone.
+fn attribute_properties(
+    attributes: &[AttributeInfo],
+) -> impl Iterator<Item = (String, String)> + '_ {
+    attributes.iter().flat_map(|attribute| {
+        let value = attribute.value.as_ref().ok();
+        let class = value
+            .filter(|_| attribute.name == "NX_class")
+            .map(|class| ("class".to_string(), class.clone()));
+        let pair = value.map(|value| ("attr".to_string(), format!("{}={value}", attribute.name)));
+        std::iter::once(("attr".to_string(), attribute.name.clone()))
+            .chain(pair)
+            .chain(class)
+    })
+}
+
+fn dtype_names(descriptor: &TypeDescriptor) -> Vec<String> {
+    let short = match descriptor {
+        TypeDescriptor::Integer(size) => Some(format!("i{}", *size as usize * 8)),
+        TypeDescriptor::Unsigned(size) => Some(format!("u{}", *size as usize * 8)),
+        TypeDescriptor::Float(size) => Some(format!("f{}", *size as usize * 8)),
+        TypeDescriptor::FixedAscii(_)
+        | TypeDescriptor::FixedUnicode(_)
+        | TypeDescriptor::VarLenAscii
+        | TypeDescriptor::VarLenUnicode => Some("str".to_string()),
+        _ => None,
+    };
+    let long = descriptor.to_string();
+    let kind = long
+        .split_whitespace()
+        .next()
+        .filter(|&kind| kind != long)
+        .map(str::to_string);
+    short.into_iter().chain(kind).chain([long]).collect()
+}
+
+const VALUE_WIDTH: usize = 12;
+
+/// The most points read for a plot.
+const MAX_PLOT_POINTS: usize = 1 << 20;
+
+/// The most values counted by a histogram.
+const MAX_HISTOGRAM_VALUES: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViewKind {
+    #[default]
+    Table,
+    Plot,
+    Histogram,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ValuesPrompt {
+    #[default]
+    Slice,
+    XAxis,
+    Ignored,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValuesState {
+    slice: Option<DatasetSlice>,
+    axis: usize,
+    row: usize,
+    column: usize,
+    page_rows: usize,
+    page_columns: usize,
+    shape: Vec<usize>,
+    view: ViewKind,
+    plot: PlotWindow,
+    /// The path and values of the x axis of plots, instead of the index.
+    x_axis: Option<(String, Vec<f64>)>,
+    /// The coordinates along each dimension, which the x axis replaces.
+    axes: Vec<Option<Axis>>,
+    prompt: ValuesPrompt,
+    input: Option<String>,
+    error: Option<String>,
+    /// Drawn with a graphics protocol once the frame has been drawn.
+    placement: Option<Placement>,
+    cache: Option<(i64, Vec<SliceInfoElem>, Result<DatasetValues, String>)>,
+    numbers_cache: Option<(i64, Vec<SliceInfoElem>, Result<ArrayD<f64>, String>)>,
+    sample_task: Option<SampleTask>,
+    samples: Option<((i64, Vec<SliceInfoElem>), Result<Vec<f64>, String>)>,
+    histogram_cache: Option<(HistogramKey, Histogram)>,
+    normalization_cache: Option<((i64, Vec<SliceInfoElem>, Scaling), Normalization)>,
+}
+
+/// Ignored values are compared by their bits, such that an ignored `nan` equals itself.
+type HistogramKey = (i64, Vec<SliceInfoElem>, usize, Vec<u64>);
+
+impl ValuesState {
+    pub fn move_up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        self.row = self.row.saturating_add(1);
     }
+
+    pub fn move_left(&mut self) {
+        match self.view {
+            ViewKind::Table => self.column = self.column.saturating_sub(1),
+            ViewKind::Plot => self.plot.pan(-1),
+            ViewKind::Histogram => {}
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        match self.view {
+            ViewKind::Table => self.column = self.column.saturating_add(1),
+            ViewKind::Plot => self.plot.pan(1),
+            ViewKind::Histogram => {}
+        }
+    }
+
+    pub fn page_up(&mut self) {
+        self.row = self.row.saturating_sub(self.page_rows.max(1));
+    }
+
+    pub fn page_down(&mut self) {
+        self.row = self.row.saturating_add(self.page_rows.max(1));
+    }
+
+    pub fn previous_axis(&mut self) {
+        self.axis = self.axis.saturating_sub(1);
+    }
+
+    pub fn next_axis(&mut self) {
+        self.axis = self
+            .axis
+            .saturating_add(1)
+            .min(self.shape.len().saturating_sub(1));
+    }
+
+    pub fn step(&mut self, delta: isize) {
+        if let Some(slice) = self.slice.as_mut() {
+            slice.step(self.axis, delta);
+        }
+    }
+
+    pub fn display_as_rows(&mut self) {
+        if let Some(slice) = self.slice.as_mut() {
+            slice.display_as_rows(self.axis);
+            self.row = 0;
+            self.column = 0;
+        }
+    }
+
+    pub fn display_as_columns(&mut self) {
+        if let Some(slice) = self.slice.as_mut() {
+            slice.display_as_columns(self.axis);
+            self.row = 0;
+            self.column = 0;
+        }
+    }
+
+    pub fn slice_expression(&self) -> String {
+        self.slice
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    pub fn edit_slice(&mut self, input: Option<String>) {
+        self.prompt = ValuesPrompt::Slice;
+        self.input = input;
+        self.error = None;
+    }
+
+    pub fn take_placement(&mut self) -> Option<Placement> {
+        self.placement.take()
+    }
+
+    pub fn is_plotting(&self) -> bool {
+        self.view == ViewKind::Plot
+    }
+
+    pub fn is_histogram(&self) -> bool {
+        self.view == ViewKind::Histogram
+    }
+
+    pub fn toggle_plot(&mut self) {
+        self.view = match self.view {
+            ViewKind::Plot => ViewKind::Table,
+            _ => ViewKind::Plot,
+        };
+    }
+
+    pub fn toggle_histogram(&mut self) {
+        self.view = match self.view {
+            ViewKind::Histogram => ViewKind::Table,
+            _ => ViewKind::Histogram,
+        };
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.plot.zoom_in();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.plot.zoom_out();
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.plot.reset();
+    }
+
+    pub fn plot_with_axes(&mut self, axes: Vec<Option<Axis>>) {
+        self.axes = axes;
+        self.view = ViewKind::Plot;
+    }
+
+    pub fn x_axis_path(&self) -> String {
+        self.x_axis
+            .as_ref()
+            .map(|(path, _)| path.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn edit_x_axis(&mut self, input: Option<String>) {
+        self.prompt = ValuesPrompt::XAxis;
+        self.input = input;
+        self.error = None;
+    }
+
+    pub fn edit_ignored(&mut self, input: Option<String>) {
+        self.prompt = ValuesPrompt::Ignored;
+        self.input = input;
+        self.error = None;
+    }
+
+    pub fn set_ignored(
+        &mut self,
+        ignored: Result<Vec<f64>, anyhow::Error>,
+    ) -> Result<Vec<f64>, anyhow::Error> {
+        match ignored {
+            Ok(ignored) => {
+                self.edit_ignored(None);
+                Ok(ignored)
+            }
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                Err(err)
+            }
+        }
+    }
+
+    pub fn set_x_axis(
+        &mut self,
+        x_axis: Result<Option<(String, Vec<f64>)>, anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        match x_axis {
+            Ok(x_axis) => {
+                self.x_axis = x_axis;
+                self.edit_x_axis(None);
+                Ok(())
+            }
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                Err(err)
+            }
+        }
+    }
+
+    pub fn apply_slice(&mut self) -> Result<(), anyhow::Error> {
+        let input = self.input.as_deref().unwrap_or_default();
+        match DatasetSlice::parse(input, &self.shape) {
+            Ok(slice) => {
+                self.slice = Some(slice);
+                self.row = 0;
+                self.column = 0;
+                self.edit_slice(None);
+                Ok(())
+            }
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                Err(err)
+            }
+        }
+    }
+
+    fn read(
+        &mut self,
+        dataset: &DatasetInfo,
+        selection: Vec<SliceInfoElem>,
+    ) -> Result<DatasetValues, String> {
+        match &self.cache {
+            Some((id, cached_selection, values))
+                if *id == dataset.id && *cached_selection == selection =>
+            {
+                values.clone()
+            }
+            _ => {
+                let values = dataset
+                    .read_values(&selection)
+                    .map_err(|err| err.to_string());
+                self.cache = Some((dataset.id, selection, values.clone()));
+                values
+            }
+        }
+    }
+
+    fn read_numbers(
+        &mut self,
+        dataset: &DatasetInfo,
+        selection: Vec<SliceInfoElem>,
+    ) -> Result<ArrayD<f64>, String> {
+        match &self.numbers_cache {
+            Some((id, cached_selection, values))
+                if *id == dataset.id && *cached_selection == selection =>
+            {
+                values.clone()
+            }
+            _ => {
+                let values = dataset
+                    .read_numbers(&selection)
+                    .map_err(|err| err.to_string());
+                self.numbers_cache = Some((dataset.id, selection, values.clone()));
+                values
+            }
+        }
+    }
+
+    /// The range mapped onto the colormap, fitted once per selection and scaling.
+    fn normalization<'a>(
+        &mut self,
+        dataset: &DatasetInfo,
+        selection: Vec<SliceInfoElem>,
+        scaling: Scaling,
+        values: impl IntoIterator<Item = &'a f64>,
+    ) -> Normalization {
+        let key = (dataset.id, selection, scaling);
+        match &self.normalization_cache {
+            Some((cached_key, normalization)) if *cached_key == key => *normalization,
+            _ => {
+                let normalization = Normalization::new(values, scaling);
+                self.normalization_cache = Some((key, normalization));
+                normalization
+            }
+        }
+    }
+
+    /// Bins the values read with the selection, returning `None` until they have been read.
+    fn histogram(
+        &mut self,
+        dataset: &DatasetInfo,
+        selection: Vec<SliceInfoElem>,
+        bins: usize,
+        ignored: &[f64],
+    ) -> Option<Result<Histogram, String>> {
+        let values = match &self.samples {
+            Some(((id, cached_selection), values))
+                if *id == dataset.id && *cached_selection == selection =>
+            {
+                values
+            }
+            _ => {
+                if !self
+                    .sample_task
+                    .as_ref()
+                    .is_some_and(|task| task.reads(dataset.id, &selection))
+                {
+                    self.sample_task = Some(SampleTask::new(dataset.clone(), selection));
+                }
+                return None;
+            }
+        };
+        let values = match values {
+            Ok(values) => values,
+            Err(err) => return Some(Err(err.clone())),
+        };
+        let key = (
+            dataset.id,
+            selection,
+            bins,
+            ignored.iter().map(|value| value.to_bits()).collect(),
+        );
+        match &self.histogram_cache {
+            Some((cached_key, histogram)) if *cached_key == key => Some(Ok(histogram.clone())),
+            _ => {
+                let histogram = Histogram::new(values.iter().copied(), bins, ignored);
+                self.histogram_cache = Some((key, histogram.clone()));
+                Some(Ok(histogram))
+            }
+        }
+    }
+
+    /// Reads the next block of the values counted by the histogram, returning whether any
+    /// remain.
+    pub fn step(&mut self) -> bool {
+        let Some(task) = self.sample_task.as_mut() else {
+            return false;
+        };
+        let result = match task.step() {
+            Ok(true) => return true,
+            Ok(false) => Ok(()),
+            Err(err) => Err(format!("{err:#}")),
+        };
+        if let Some(task) = self.sample_task.take() {
+            let key = (task.dataset_id(), task.selection().to_vec());
+            self.samples = Some((key, result.map(|()| task.into_values())));
+        }
+        false
+    }
+}
+
+/// A scrollable view of a slice of a dataset, which reads only the visible window.
+struct ValuesView<'a> {
+    dataset: &'a DatasetInfo,
+    focused: bool,
+    heatmap: HeatmapOptions,
+    histogram: &'a HistogramOptions,
+}
+
+impl<'a> ValuesView<'a> {
+    fn new(
+        dataset: &'a DatasetInfo,
+        focused: bool,
+        heatmap: HeatmapOptions,
+        histogram: &'a HistogramOptions,
+    ) -> Self {
+        Self {
+            dataset,
+            focused,
+            heatmap,
+            histogram,
+        }
+    }
+
+    fn title(&self, slice: &DatasetSlice, axis: usize, view: ViewKind) -> Line<'static> {
+        let mut spans = vec![Span::raw(match view {
+            ViewKind::Table => "Values",
+            ViewKind::Plot => "Plot",
+            ViewKind::Histogram => "Histogram",
+        })];
+        if slice.ndim() > 0 {
+            spans.push(Span::raw(" ["));
+            for (idx, term) in slice.terms().into_iter().enumerate() {
+                if idx > 0 {
+                    spans.push(Span::raw(", "));
+                }
+                if self.focused && idx == axis {
+                    spans.push(Span::styled(
+                        term,
+                        Style::new().add_modifier(Modifier::REVERSED),
+                    ));
+                } else {
+                    spans.push(Span::raw(term));
+                }
+            }
+            spans.push(Span::raw("]"));
+        }
+        if view == ViewKind::Plot && slice.columns().is_some() {
+            spans.push(Span::raw(format!(
+                " {}, {}",
+                self.heatmap.colormap, self.heatmap.scaling
+            )));
+        }
+        if view == ViewKind::Histogram {
+            spans.push(Span::raw(format!(" {}", self.histogram.scope)));
+            if self.histogram.log_counts {
+                spans.push(Span::raw(", Log Counts"));
+            }
+            if !self.histogram.ignored.is_empty() {
+                spans.push(Span::raw(format!(
+                    ", Ignoring {}",
+                    self.histogram.ignored_expression()
+                )));
+            }
+        }
+        Line::from(spans)
+    }
+
+    fn render_value(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+    ) {
+        match state.read(self.dataset, slice.selection(0..1, 0..1)) {
+            Ok(values) => Paragraph::new(values.values.join("")).render(area, buf),
+            Err(err) => render_error(err, area, buf),
+        }
+    }
+
+    fn render_list(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+        rows_axis: usize,
+    ) {
+        let length = slice.len(rows_axis);
+        state.page_rows = area.height as usize;
+        state.row = state.row.min(length.saturating_sub(state.page_rows));
+        let end = (state.row + state.page_rows).min(length);
+        let values = match state.read(self.dataset, slice.selection(state.row..end, 0..0)) {
+            Ok(values) => values,
+            Err(err) => return render_error(err, area, buf),
+        };
+        let index_width = slice
+            .coordinate(rows_axis, length.saturating_sub(1))
+            .to_string()
+            .len() as u16;
+        let table = Table::new(
+            values.values.into_iter().enumerate().map(|(idx, value)| {
+                Row::new(vec![
+                    Cell::from(
+                        Text::raw(slice.coordinate(rows_axis, state.row + idx).to_string())
+                            .right_aligned(),
+                    )
+                    .style(Style::new().add_modifier(Modifier::DIM)),
+                    Cell::from(value),
+                ])
+            }),
+            [Constraint::Length(index_width), Constraint::Min(0)],
+        );
+        Widget::render(table, area, buf);
+    }
+
+    fn render_grid(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+        (rows_axis, columns_axis): (usize, usize),
+    ) {
+        let (rows, columns) = (slice.len(rows_axis), slice.len(columns_axis));
+        let index_width = slice
+            .coordinate(rows_axis, rows.saturating_sub(1))
+            .to_string()
+            .len();
+        state.page_rows = (area.height as usize).saturating_sub(1);
+        state.page_columns =
+            ((area.width as usize).saturating_sub(index_width) / (VALUE_WIDTH + 1)).max(1);
+        state.row = state.row.min(rows.saturating_sub(state.page_rows));
+        state.column = state.column.min(columns.saturating_sub(state.page_columns));
+        let row_end = (state.row + state.page_rows).min(rows);
+        let column_end = (state.column + state.page_columns).min(columns);
+        let values = match state.read(
+            self.dataset,
+            slice.selection(state.row..row_end, state.column..column_end),
+        ) {
+            Ok(values) => values,
+            Err(err) => return render_error(err, area, buf),
+        };
+        let (height, width) = (row_end - state.row, column_end - state.column);
+        // Values are read in dataset order, so are transposed if the rows follow the columns.
+        let value = |row: usize, column: usize| {
+            let idx = if rows_axis < columns_axis {
+                row * width + column
+            } else {
+                column * height + row
+            };
+            values.values.get(idx).cloned().unwrap_or_default()
+        };
+        let index_style = Style::new().add_modifier(Modifier::DIM);
+        let header = Row::new(std::iter::once(Cell::default()).chain(
+            (state.column..column_end).map(|column| {
+                Cell::from(
+                    Text::raw(slice.coordinate(columns_axis, column).to_string()).right_aligned(),
+                )
+            }),
+        ))
+        .style(index_style);
+        let table =
+            Table::new(
+                (0..height).map(|row| {
+                    Row::new(
+                        std::iter::once(
+                            Cell::from(
+                                Text::raw(slice.coordinate(rows_axis, state.row + row).to_string())
+                                    .right_aligned(),
+                            )
+                            .style(index_style),
+                        )
+                        .chain((0..width).map(|column| {
+                            Cell::from(Text::raw(value(row, column)).right_aligned())
+                        })),
+                    )
+                }),
+                std::iter::once(Constraint::Length(index_width as u16)).chain(std::iter::repeat_n(
+                    Constraint::Length(VALUE_WIDTH as u16),
+                    width,
+                )),
+            )
+            .header(header);
+        Widget::render(table, area, buf);
+    }
+
+    fn render_plot(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+    ) {
+        let rows_axis = match (slice.rows(), slice.columns()) {
+            (Some(rows_axis), None) => rows_axis,
+            (Some(rows_axis), Some(columns_axis)) => {
+                return self.render_heatmap(area, buf, state, slice, (rows_axis, columns_axis))
+            }
+            _ => return render_error("Scalars cannot be plotted".to_string(), area, buf),
+        };
+        let window = state.plot.range(slice.len(rows_axis));
+        let stride = window.len().div_ceil(MAX_PLOT_POINTS).max(1);
+        let selection = slice.strided_selection(window.clone(), 0..0, (stride, 1));
+        let values = match state.read_numbers(self.dataset, selection) {
+            Ok(values) => values,
+            Err(err) => return render_error(err, area, buf),
+        };
+        let length = self.dataset.shape[rows_axis];
+        let x_axis = state
+            .x_axis
+            .as_ref()
+            .or_else(|| state.axes.get(rows_axis)?.as_ref());
+        match x_axis {
+            // Axes may hold the edges of bins, with one more value than the plotted dimension.
+            Some((path, x_values)) if x_values.len() != length && x_values.len() != length + 1 => {
+                return render_error(
+                    format!(
+                        "The x axis {path} has {} values, but the plotted dimension has {length}",
+                        x_values.len()
+                    ),
+                    area,
+                    buf,
+                )
+            }
+            _ => {}
+        }
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(idx, &value)| {
+                let coordinate = slice.coordinate(rows_axis, window.start + idx * stride);
+                let x = x_axis.map_or(coordinate as f64, |(_, x_values)| x_values[coordinate]);
+                (x, value)
+            })
+            .collect();
+        // Braille characters hold two points across each cell.
+        let points = decimate(points, area.width as usize * 2);
+        let x_title = x_axis.map_or("Index".to_string(), |(path, _)| path.clone());
+        LinePlot::new(&points, x_title, theme().dataset).render(area, buf);
+    }
+
+    fn render_heatmap(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+        (rows_axis, columns_axis): (usize, usize),
+    ) {
+        let (rows, columns) = (slice.len(rows_axis), slice.len(columns_axis));
+        let legend = [("x", columns_axis), ("y", rows_axis)]
+            .into_iter()
+            .filter_map(|(name, axis)| {
+                let (label, values) = state.axes.get(axis)?.as_ref()?;
+                let first = values.get(slice.coordinate(axis, 0))?;
+                let last = values.get(slice.coordinate(axis, slice.len(axis).saturating_sub(1)))?;
+                Some(format!(
+                    "{name}: {label} from {} to {}",
+                    format_number(*first),
+                    format_number(*last)
+                ))
+            })
+            .collect::<Vec<_>>();
+        let area = if legend.is_empty() || area.height < 2 {
+            area
+        } else {
+            let legend_area = Rect {
+                y: area.bottom() - 1,
+                height: 1,
+                ..area
+            };
+            Paragraph::new(legend.join(", "))
+                .style(Style::new().add_modifier(Modifier::DIM))
+                .render(legend_area, buf);
+            Rect {
+                height: area.height - 1,
+                ..area
+            }
+        };
+        let (pixel_rows, pixel_columns) = image_size(area, self.heatmap.cell_size);
+        if pixel_rows == 0 || pixel_columns == 0 {
+            return;
+        }
+        // The same stride is used along both dimensions, such that the aspect ratio is kept.
+        let stride = rows
+            .div_ceil(pixel_rows)
+            .max(columns.div_ceil(pixel_columns))
+            .max(1);
+        let selection = slice.strided_selection(0..rows, 0..columns, (stride, stride));
+        let values = match state
+            .read_numbers(self.dataset, selection.clone())
+            .and_then(|values| {
+                values
+                    .into_dimensionality::<Ix2>()
+                    .map_err(|err| err.to_string())
+            }) {
+            Ok(values) => values,
+            Err(err) => return render_error(err, area, buf),
+        };
+        // Values are read in dataset order, so are transposed if the rows follow the columns.
+        let values = if rows_axis < columns_axis {
+            values
+        } else {
+            values.reversed_axes()
+        };
+        let normalization =
+            state.normalization(self.dataset, selection, self.heatmap.scaling, values.iter());
+        Heatmap::new(values.view(), self.heatmap, normalization).render(
+            area,
+            buf,
+            &mut state.placement,
+        );
+    }
+
+    fn render_histogram(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ValuesState,
+        slice: &DatasetSlice,
+    ) {
+        let (selection, stride) = match self.histogram.scope {
+            HistogramScope::Slice => {
+                let rows = slice.rows().map_or(1, |axis| slice.len(axis));
+                let columns = slice.columns().map_or(1, |axis| slice.len(axis));
+                let lengths = [slice.rows(), slice.columns()]
+                    .into_iter()
+                    .flatten()
+                    .map(|axis| slice.len(axis))
+                    .collect::<Vec<_>>();
+                let stride = sampling_stride(&lengths);
+                (
+                    slice.strided_selection(0..rows, 0..columns, (stride, stride)),
+                    stride,
+                )
+            }
+            HistogramScope::Dataset => {
+                let stride = sampling_stride(&self.dataset.shape);
+                let selection = self
+                    .dataset
+                    .shape
+                    .iter()
+                    .map(|_| SliceInfoElem::Slice {
+                        start: 0,
+                        end: None,
+                        step: stride as isize,
+                    })
+                    .collect();
+                (selection, stride)
+            }
+        };
+        let bins = self.histogram.bins.min(area.width as usize).max(1);
+        match state.histogram(self.dataset, selection, bins, &self.histogram.ignored) {
+            Some(Ok(histogram)) => HistogramChart::new(
+                &histogram,
+                self.histogram.log_counts,
+                stride,
+                theme().dataset,
+            )
+            .render(area, buf),
+            Some(Err(err)) => render_error(err, area, buf),
+            None => {
+                let progress = state.sample_task.as_ref().map_or(0.0, SampleTask::progress);
+                Paragraph::new(format!("Reading values {}", progress_bar(progress)))
+                    .render(area, buf)
+            }
+        }
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer, state: &ValuesState) {
+        let Some(input) = state.input.as_ref() else {
+            return;
+        };
+        let label = match state.prompt {
+            ValuesPrompt::Slice => "Slice",
+            ValuesPrompt::XAxis => "X Axis",
+            ValuesPrompt::Ignored => "Ignore",
+        };
+        let prompt = format!("{label}: {input}");
+        let cursor_area = Rect::new(
+            area.left() + prompt.chars().count() as u16,
+            area.top(),
+            1,
+            1,
+        )
+        .intersection(area);
+        let mut spans = vec![Span::raw(prompt)];
+        if let Some(error) = state.error.as_ref() {
+            spans.push(Span::styled(
+                format!("  {error}"),
+                Style::new().fg(Color::Red),
+            ));
+        }
+        Paragraph::new(Line::from(spans)).render(area, buf);
+        buf.set_style(cursor_area, Style::new().bg(Color::White));
+    }
+}
+
+impl StatefulWidget for ValuesView<'_> {
+    type State = ValuesState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let shape = &self.dataset.shape;
+        state.placement = None;
+        if state.shape != *shape {
+            state.shape.clone_from(shape);
+            state.slice = None;
+        }
+        let slice = state
+            .slice
+            .get_or_insert_with(|| DatasetSlice::new(shape))
+            .clone();
+        state.axis = state.axis.min(shape.len().saturating_sub(1));
+        let title_style = if self.focused {
+            Style::new().add_modifier(Modifier::BOLD)
+        } else {
+            Style::new()
+        };
+        let block = Block::default()
+            .title(self.title(&slice, state.axis, state.view))
+            .title_style(title_style)
+            .borders(Borders::TOP);
+        let mut inner_area = block.inner(area);
+        block.render(area, buf);
+        if state.input.is_some() && inner_area.height > 0 {
+            let prompt_area = Rect {
+                y: inner_area.bottom() - 1,
+                height: 1,
+                ..inner_area
+            };
+            inner_area.height -= 1;
+            self.render_prompt(prompt_area, buf, state);
+        }
+        if shape.contains(&0) {
+            Paragraph::new("Empty dataset").render(inner_area, buf);
+            return;
+        }
+        match state.view {
+            ViewKind::Table => {}
+            ViewKind::Plot => return self.render_plot(inner_area, buf, state, &slice),
+            ViewKind::Histogram => return self.render_histogram(inner_area, buf, state, &slice),
+        }
+        match (slice.rows(), slice.columns()) {
+            (Some(rows_axis), Some(columns_axis)) => {
+                self.render_grid(inner_area, buf, state, &slice, (rows_axis, columns_axis))
+            }
+            (Some(rows_axis), None) => self.render_list(inner_area, buf, state, &slice, rows_axis),
+            _ => self.render_value(inner_area, buf, state, &slice),
+        }
+    }
+}
+
+/// The smallest step along every dimension which reads at most [`MAX_HISTOGRAM_VALUES`].
+fn sampling_stride(lengths: &[usize]) -> usize {
+    let count = |stride: usize| {
+        lengths
+            .iter()
+            .map(|length| length.div_ceil(stride))
+            .product::<usize>()
+    };
+    let mut stride = 1;
+    while count(stride) > MAX_HISTOGRAM_VALUES {
+        stride += 1;
+    }
+    stride
+}
+
+fn render_error(err: String, area: Rect, buf: &mut Buffer) {
+    Paragraph::new(err)
+        .style(Style::new().fg(Color::Red))
+        .render(area, buf);
 }
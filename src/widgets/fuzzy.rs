@@ -0,0 +1,123 @@
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CONSECUTIVE: i64 = 4;
+const BONUS_NAME: i64 = 2;
+const PENALTY_GAP: i64 = 1;
+
+/// A fuzzy match, where higher scores are better matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// The character positions of the matched characters.
+    pub positions: Vec<usize>,
+}
+
+/// Finds the characters of the pattern in order within the text, in the style of fzf, ignoring
+/// case unless the pattern contains an uppercase character.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let normalize = |char: char| {
+        if case_sensitive {
+            char
+        } else {
+            char.to_lowercase().next().unwrap_or(char)
+        }
+    };
+    let pattern = pattern.chars().map(normalize).collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    // Find where the earliest occurrence of the pattern ends, then walk backwards from there to
+    // find the shortest occurrence ending at the same character.
+    let mut matched = 0;
+    let end = text.iter().position(|&char| {
+        if normalize(char) == pattern[matched] {
+            matched += 1;
+        }
+        matched == pattern.len()
+    })?;
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut remaining = pattern.len();
+    for position in (0..=end).rev() {
+        if normalize(text[position]) == pattern[remaining - 1] {
+            positions.push(position);
+            remaining -= 1;
+            if remaining == 0 {
+                break;
+            }
+        }
+    }
+    positions.reverse();
+
+    let name_start = text
+        .iter()
+        .rposition(|&char| char == '/')
+        .map_or(0, |slash| slash + 1);
+    let score = positions
+        .iter()
+        .enumerate()
+        .map(|(idx, &position)| {
+            let mut score = SCORE_MATCH;
+            if position == 0 || is_boundary(text[position - 1], text[position]) {
+                score += BONUS_BOUNDARY;
+            }
+            if position >= name_start {
+                score += BONUS_NAME;
+            }
+            if idx > 0 {
+                match position - positions[idx - 1] - 1 {
+                    0 => score += BONUS_CONSECUTIVE,
+                    gap => score -= PENALTY_GAP * gap as i64,
+                }
+            }
+            score
+        })
+        .sum();
+    Some(FuzzyMatch { score, positions })
+}
+
+fn is_boundary(previous: char, char: char) -> bool {
+    !previous.is_alphanumeric()
+        || (previous.is_lowercase() && char.is_uppercase())
+        || (!previous.is_ascii_digit() && char.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(pattern: &str, text: &str) -> i64 {
+        fuzzy_match(pattern, text).unwrap().score
+    }
+
+    #[test]
+    fn matches_the_shortest_occurrence() {
+        let found = fuzzy_match("detdata", "/entry/instrument/detector/data").unwrap();
+        assert_eq!(found.positions, vec![18, 21, 23, 27, 28, 29, 30]);
+    }
+
+    #[test]
+    fn requires_characters_in_order() {
+        assert_eq!(fuzzy_match("atad", "/entry/data"), None);
+        assert_eq!(fuzzy_match("", "/entry").map(|found| found.score), Some(0));
+    }
+
+    #[test]
+    fn ignores_case_unless_the_pattern_has_uppercase() {
+        assert!(fuzzy_match("det", "/Detector").is_some());
+        assert!(fuzzy_match("Det", "/detector").is_none());
+        assert!(fuzzy_match("Det", "/Detector").is_some());
+    }
+
+    #[test]
+    fn ranks_consecutive_name_matches_higher() {
+        assert!(score("data", "/entry/data") > score("data", "/entry/dxaxtxa"));
+        assert!(score("data", "/entry/data") > score("data", "/data/entry"));
+        assert!(score("dd", "/detector_data") > score("dd", "/odd"));
+    }
+}
@@ -0,0 +1,416 @@
+use super::plot::format_number;
+use crate::graphics::{Placement, RgbImage};
+use ndarray::ArrayView2;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::StatefulWidget,
+};
+use std::fmt::Display;
+
+/// The width of the color bar, including its labels.
+const COLOR_BAR_WIDTH: u16 = 12;
+const PERCENTILE_CLIP: f64 = 0.01;
+/// Log scaling spans three decades whatever the units of the values.
+const LOG_STRETCH: f64 = 1000.0;
+const UPPER_HALF_BLOCK: &str = "\u{2580}";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Colormap {
+    #[default]
+    Viridis,
+    Gray,
+    Inferno,
+}
+
+impl Colormap {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Viridis => Self::Gray,
+            Self::Gray => Self::Inferno,
+            Self::Inferno => Self::Viridis,
+        }
+    }
+
+    /// The color of a value from zero to one.
+    pub fn color(self, value: f64) -> Color {
+        let [r, g, b] = self.rgb(value);
+        Color::Rgb(r, g, b)
+    }
+
+    fn rgb(self, value: f64) -> [u8; 3] {
+        let stops: &[[u8; 3]] = match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Gray => &[[0, 0, 0], [255, 255, 255]],
+            Self::Inferno => &INFERNO,
+        };
+        let position = value.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let idx = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - idx as f64;
+        [0, 1, 2].map(|channel| {
+            let (start, end) = (stops[idx][channel] as f64, stops[idx + 1][channel] as f64);
+            (start + (end - start) * fraction).round() as u8
+        })
+    }
+}
+
+impl Display for Colormap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Viridis => "Viridis",
+            Self::Gray => "Gray",
+            Self::Inferno => "Inferno",
+        })
+    }
+}
+
+/// Samples of the matplotlib colormaps.
+const VIRIDIS: [[u8; 3]; 10] = [
+    [0x44, 0x01, 0x54],
+    [0x48, 0x28, 0x78],
+    [0x3e, 0x4a, 0x89],
+    [0x31, 0x68, 0x8e],
+    [0x26, 0x82, 0x8e],
+    [0x1f, 0x9e, 0x89],
+    [0x35, 0xb7, 0x79],
+    [0x6d, 0xcd, 0x59],
+    [0xb4, 0xde, 0x2c],
+    [0xfd, 0xe7, 0x25],
+];
+
+const INFERNO: [[u8; 3]; 10] = [
+    [0x00, 0x00, 0x04],
+    [0x1b, 0x0c, 0x42],
+    [0x4b, 0x0c, 0x6b],
+    [0x78, 0x1c, 0x6d],
+    [0xa5, 0x2c, 0x60],
+    [0xcf, 0x44, 0x46],
+    [0xed, 0x69, 0x25],
+    [0xfb, 0x9a, 0x06],
+    [0xf7, 0xd0, 0x3c],
+    [0xfc, 0xff, 0xa4],
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeatmapOptions {
+    pub colormap: Colormap,
+    pub scaling: Scaling,
+    /// The size of a cell in pixels, if images can be drawn with a graphics protocol.
+    pub cell_size: Option<(u16, u16)>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scaling {
+    #[default]
+    Linear,
+    Log,
+    /// Linearly between the first and last percentiles, ignoring outliers.
+    Percentile,
+}
+
+impl Scaling {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Linear => Self::Log,
+            Self::Log => Self::Percentile,
+            Self::Percentile => Self::Linear,
+        }
+    }
+}
+
+impl Display for Scaling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Linear => "Linear",
+            Self::Log => "Log",
+            Self::Percentile => "Percentile",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalization {
+    min: f64,
+    max: f64,
+    scaling: Scaling,
+}
+
+impl Normalization {
+    /// Fits the range to the finite values, which sorts them for percentile scaling.
+    pub fn new<'a>(values: impl IntoIterator<Item = &'a f64>, scaling: Scaling) -> Self {
+        let finite = values
+            .into_iter()
+            .copied()
+            .filter(|value| value.is_finite());
+        let range = match scaling {
+            Scaling::Linear | Scaling::Log => finite.fold(None, |range, value| match range {
+                Some((min, max)) => Some((value.min(min), value.max(max))),
+                None => Some((value, value)),
+            }),
+            Scaling::Percentile => {
+                let mut finite = finite.collect::<Vec<_>>();
+                finite.sort_unstable_by(f64::total_cmp);
+                finite.len().checked_sub(1).map(|last| {
+                    let clip = (last as f64 * PERCENTILE_CLIP).round() as usize;
+                    (finite[clip], finite[last - clip])
+                })
+            }
+        };
+        let (min, max) = range.unwrap_or((0.0, 1.0));
+        Self { min, max, scaling }
+    }
+
+    /// The position of the value within the range, from zero to one.
+    fn apply(&self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return None;
+        }
+        let range = self.max - self.min;
+        if range <= 0.0 {
+            return Some(0.5);
+        }
+        let position = (value - self.min) / range;
+        let position = match self.scaling {
+            Scaling::Linear | Scaling::Percentile => position,
+            Scaling::Log => (LOG_STRETCH * position.max(0.0)).ln_1p() / LOG_STRETCH.ln_1p(),
+        };
+        Some(position.clamp(0.0, 1.0))
+    }
+
+    fn value_at(&self, position: f64) -> f64 {
+        let range = self.max - self.min;
+        match self.scaling {
+            Scaling::Linear | Scaling::Percentile => self.min + position * range,
+            Scaling::Log => {
+                self.min + range * (position * LOG_STRETCH.ln_1p()).exp_m1() / LOG_STRETCH
+            }
+        }
+    }
+}
+
+/// An image of two dimensional values with a color bar, drawn with half block characters or,
+/// if the size of a cell is known, at the resolution of the terminal.
+pub struct Heatmap<'a> {
+    values: ArrayView2<'a, f64>,
+    colormap: Colormap,
+    normalization: Normalization,
+    cell_size: Option<(u16, u16)>,
+}
+
+impl<'a> Heatmap<'a> {
+    pub fn new(
+        values: ArrayView2<'a, f64>,
+        options: HeatmapOptions,
+        normalization: Normalization,
+    ) -> Self {
+        Self {
+            normalization,
+            values,
+            colormap: options.colormap,
+            cell_size: options.cell_size,
+        }
+    }
+
+    fn color(&self, value: f64) -> Color {
+        self.normalization
+            .apply(value)
+            .map_or(Color::Reset, |position| self.colormap.color(position))
+    }
+
+    /// The largest size in pixels which fits while keeping the aspect ratio.
+    fn fit(&self, width: usize, height: usize) -> (usize, usize) {
+        let (rows, columns) = self.values.dim();
+        let scale = (width as f64 / columns as f64).min(height as f64 / rows as f64);
+        (
+            ((columns as f64 * scale).round() as usize).clamp(1, width),
+            ((rows as f64 * scale).round() as usize).clamp(1, height),
+        )
+    }
+
+    fn pixel(&self, x: usize, y: usize, (width, height): (usize, usize)) -> f64 {
+        let (rows, columns) = self.values.dim();
+        self.values[[y * rows / height, x * columns / width]]
+    }
+
+    fn render_image(&self, area: Rect, buf: &mut Buffer) {
+        let (rows, columns) = self.values.dim();
+        if rows == 0 || columns == 0 || area.is_empty() {
+            return;
+        }
+        let size @ (width, height) = self.fit(area.width as usize, area.height as usize * 2);
+        let color = |x: usize, y: usize| {
+            if y < height {
+                self.color(self.pixel(x, y, size))
+            } else {
+                Color::Reset
+            }
+        };
+        for y in 0..height.div_ceil(2) {
+            for x in 0..width {
+                if let Some(cell) = buf.cell_mut((area.x + x as u16, area.y + y as u16)) {
+                    cell.set_symbol(UPPER_HALF_BLOCK)
+                        .set_fg(color(x, 2 * y))
+                        .set_bg(color(x, 2 * y + 1));
+                }
+            }
+        }
+    }
+
+    /// Marks the cells covered by the image, such that ratatui does not draw over it.
+    fn render_pixels(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        (cell_width, cell_height): (u16, u16),
+    ) -> Option<Placement> {
+        let (rows, columns) = self.values.dim();
+        if rows == 0 || columns == 0 || area.is_empty() || cell_width == 0 || cell_height == 0 {
+            return None;
+        }
+        let size @ (width, height) = self.fit(
+            area.width as usize * cell_width as usize,
+            area.height as usize * cell_height as usize,
+        );
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                self.normalization
+                    .apply(self.pixel(x, y, size))
+                    .map_or([0, 0, 0], |position| self.colormap.rgb(position))
+            })
+            .collect();
+        let placement_area = Rect {
+            width: (width.div_ceil(cell_width as usize) as u16).min(area.width),
+            height: (height.div_ceil(cell_height as usize) as u16).min(area.height),
+            ..area
+        };
+        for position in placement_area.positions() {
+            if let Some(cell) = buf.cell_mut(position) {
+                cell.set_skip(true);
+            }
+        }
+        Some(Placement {
+            area: placement_area,
+            image: RgbImage {
+                width,
+                height,
+                pixels,
+            },
+        })
+    }
+
+    fn render_color_bar(&self, area: Rect, buf: &mut Buffer) {
+        let pixel_height = area.height as usize * 2;
+        if pixel_height < 2 {
+            return;
+        }
+        let position = |pixel: usize| 1.0 - pixel as f64 / (pixel_height - 1) as f64;
+        for y in 0..area.height {
+            let upper = self.colormap.color(position(2 * y as usize));
+            let lower = self.colormap.color(position(2 * y as usize + 1));
+            for x in area.left() + 1..area.left() + 3 {
+                if let Some(cell) = buf.cell_mut((x, area.y + y)) {
+                    cell.set_symbol(UPPER_HALF_BLOCK)
+                        .set_fg(upper)
+                        .set_bg(lower);
+                }
+            }
+        }
+        let labels = [
+            (area.top(), 1.0),
+            (area.top() + area.height / 2, 0.5),
+            (area.bottom() - 1, 0.0),
+        ];
+        for (y, position) in labels {
+            buf.set_stringn(
+                area.left() + 4,
+                y,
+                format_number(self.normalization.value_at(position)),
+                area.width.saturating_sub(4) as usize,
+                Style::new(),
+            );
+        }
+    }
+}
+
+impl StatefulWidget for Heatmap<'_> {
+    type State = Option<Placement>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, placement: &mut Self::State) {
+        let (image_area, color_bar_area) = split(area);
+        if let Some(color_bar_area) = color_bar_area {
+            self.render_color_bar(color_bar_area, buf);
+        }
+        *placement = match self.cell_size {
+            Some(cell_size) => self.render_pixels(image_area, buf, cell_size),
+            None => {
+                self.render_image(image_area, buf);
+                None
+            }
+        };
+    }
+}
+
+fn split(area: Rect) -> (Rect, Option<Rect>) {
+    if area.width > 2 * COLOR_BAR_WIDTH {
+        let image_area = Rect {
+            width: area.width - COLOR_BAR_WIDTH,
+            ..area
+        };
+        let color_bar_area = Rect {
+            x: image_area.right(),
+            width: COLOR_BAR_WIDTH,
+            ..area
+        };
+        (image_area, Some(color_bar_area))
+    } else {
+        (area, None)
+    }
+}
+
+/// The number of rows and columns of pixels which can be drawn beside the color bar.
+pub fn image_size(area: Rect, cell_size: Option<(u16, u16)>) -> (usize, usize) {
+    let (image_area, _) = split(area);
+    let (cell_width, cell_height) = cell_size.unwrap_or((1, 2));
+    (
+        image_area.height as usize * cell_height as usize,
+        image_area.width as usize * cell_width as usize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_scaling_does_not_depend_on_units() {
+        let small = Normalization::new(&[0.0, 0.001], Scaling::Log);
+        let large = Normalization::new(&[0.0, 1e9], Scaling::Log);
+        for position in [0.1, 0.5, 0.9] {
+            let value = small.value_at(position);
+            let scaled = small.apply(value).unwrap();
+            assert!((scaled - position).abs() < 1e-9);
+            assert!((large.apply(value * 1e12).unwrap() - scaled).abs() < 1e-9);
+        }
+        assert!(small.apply(0.0001).unwrap() > 0.5);
+    }
+
+    #[test]
+    fn percentile_scaling_clips_outliers() {
+        let mut values = (0..1000).map(f64::from).collect::<Vec<_>>();
+        values.push(1e9);
+        let normalization = Normalization::new(&values, Scaling::Percentile);
+        assert_eq!((normalization.min, normalization.max), (10.0, 990.0));
+        let normalization = Normalization::new(&values, Scaling::Linear);
+        assert_eq!((normalization.min, normalization.max), (0.0, 1e9));
+    }
+
+    #[test]
+    fn non_finite_values_are_ignored() {
+        let normalization = Normalization::new(&[f64::NAN, 2.0, f64::INFINITY], Scaling::Linear);
+        assert_eq!((normalization.min, normalization.max), (2.0, 2.0));
+        assert_eq!(normalization.apply(f64::NAN), None);
+        assert_eq!(normalization.apply(2.0), Some(0.5));
+    }
+}
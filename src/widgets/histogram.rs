@@ -0,0 +1,290 @@
+use super::plot::format_number;
+use anyhow::Context;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Paragraph, Widget},
+};
+use std::fmt::Display;
+
+const DEFAULT_BINS: usize = 32;
+const MIN_BINS: usize = 2;
+const MAX_BINS: usize = 1024;
+const MIN_LABELLED_WIDTH: u16 = 8;
+/// Bars are drawn from integers, so logarithmic counts are scaled up.
+const LOG_SCALE: f64 = 1000.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistogramScope {
+    #[default]
+    Slice,
+    Dataset,
+}
+
+impl HistogramScope {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Slice => Self::Dataset,
+            Self::Dataset => Self::Slice,
+        }
+    }
+}
+
+impl Display for HistogramScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Slice => "Slice",
+            Self::Dataset => "Whole Dataset",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramOptions {
+    pub bins: usize,
+    pub log_counts: bool,
+    pub scope: HistogramScope,
+    /// Values which are not counted, such as fill values.
+    pub ignored: Vec<f64>,
+}
+
+impl Default for HistogramOptions {
+    fn default() -> Self {
+        Self {
+            bins: DEFAULT_BINS,
+            log_counts: false,
+            scope: HistogramScope::default(),
+            ignored: vec![],
+        }
+    }
+}
+
+impl HistogramOptions {
+    pub fn more_bins(&mut self) {
+        self.bins = (self.bins * 2).min(MAX_BINS);
+    }
+
+    pub fn fewer_bins(&mut self) {
+        self.bins = (self.bins / 2).max(MIN_BINS);
+    }
+
+    pub fn ignored_expression(&self) -> String {
+        self.ignored
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Parses a comma separated list of values, which may be hexadecimal.
+pub fn parse_ignored(input: &str) -> Result<Vec<f64>, anyhow::Error> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let value = match term.strip_prefix("0x").or_else(|| term.strip_prefix("0X")) {
+                Some(digits) => u64::from_str_radix(digits, 16)
+                    .ok()
+                    .map(|value| value as f64),
+                None => term.parse::<f64>().ok(),
+            };
+            value.with_context(|| format!("Invalid value {term}"))
+        })
+        .collect()
+}
+
+/// The counts of equal bins spanning the finite values which are not ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    min: f64,
+    max: f64,
+    counts: Vec<u64>,
+    excluded: u64,
+}
+
+impl Histogram {
+    pub fn new(values: impl Iterator<Item = f64> + Clone, bins: usize, ignored: &[f64]) -> Self {
+        let counted = values
+            .clone()
+            .filter(|value| value.is_finite() && !ignored.contains(value));
+        let (min, max) = counted
+            .clone()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        let bins = bins.max(1);
+        let width = (max - min) / bins as f64;
+        let mut counts = vec![0; bins];
+        for value in counted {
+            let bin = if width > 0.0 {
+                ((value - min) / width) as usize
+            } else {
+                0
+            };
+            // The largest value lies on the upper edge of the last bin.
+            counts[bin.min(bins - 1)] += 1;
+        }
+        let excluded = values.count() as u64 - counts.iter().sum::<u64>();
+        Self {
+            min,
+            max,
+            counts,
+            excluded,
+        }
+    }
+
+    fn counted(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+}
+
+pub struct HistogramChart<'a> {
+    histogram: &'a Histogram,
+    log_counts: bool,
+    /// The step between the values counted along each dimension.
+    stride: usize,
+    color: Color,
+}
+
+impl<'a> HistogramChart<'a> {
+    pub fn new(histogram: &'a Histogram, log_counts: bool, stride: usize, color: Color) -> Self {
+        Self {
+            histogram,
+            log_counts,
+            stride,
+            color,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let histogram = self.histogram;
+        let mut summary = format!(
+            "{} bins of {} from {} to {}, {} counted, {} excluded",
+            histogram.counts.len(),
+            format_number(histogram.bin_width()),
+            format_number(histogram.min),
+            format_number(histogram.max),
+            histogram.counted(),
+            histogram.excluded
+        );
+        if self.stride > 1 {
+            summary.push_str(&format!(", every {} along each dimension", self.stride));
+        }
+        summary
+    }
+}
+
+impl Widget for HistogramChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.histogram.counted() == 0 {
+            Paragraph::new(format!(
+                "No values to count, {} excluded",
+                self.histogram.excluded
+            ))
+            .render(area, buf);
+            return;
+        }
+        let [chart_area, summary_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        let bins = self.histogram.counts.len() as u16;
+        let slot_width = (chart_area.width / bins.max(1)).max(1);
+        let gap = u16::from(slot_width >= 3);
+        let bar_width = slot_width - gap;
+        let bars = self
+            .histogram
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(bin, &count)| {
+                let value = if self.log_counts {
+                    ((count as f64).ln_1p() * LOG_SCALE).round() as u64
+                } else {
+                    count
+                };
+                let bar = Bar::default().value(value).text_value(count.to_string());
+                if bar_width >= MIN_LABELLED_WIDTH {
+                    let lower = self.histogram.min + bin as f64 * self.histogram.bin_width();
+                    bar.label(Line::raw(format_number(lower)))
+                } else {
+                    bar
+                }
+            })
+            .collect::<Vec<_>>();
+        BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(gap)
+            .bar_style(Style::new().fg(self.color))
+            .value_style(Style::new().fg(Color::Black).bg(self.color))
+            .render(chart_area, buf);
+        Paragraph::new(self.summary())
+            .style(Style::new().add_modifier(Modifier::DIM))
+            .render(summary_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bins_values_evenly() {
+        let histogram = Histogram::new((0..10).map(f64::from), 5, &[]);
+        assert_eq!((histogram.min, histogram.max), (0.0, 9.0));
+        assert_eq!(histogram.counts, vec![2, 2, 2, 2, 2]);
+        assert_eq!(histogram.excluded, 0);
+    }
+
+    #[test]
+    fn counts_the_maximum_in_the_last_bin() {
+        let histogram = Histogram::new([0.0, 1.0, 2.0].into_iter(), 2, &[]);
+        assert_eq!(histogram.counts, vec![1, 2]);
+    }
+
+    #[test]
+    fn excludes_ignored_and_non_finite_values() {
+        let values = [1.0, 2.0, f64::NAN, f64::INFINITY, 65535.0, 3.0];
+        let histogram = Histogram::new(values.into_iter(), 2, &[65535.0]);
+        assert_eq!((histogram.min, histogram.max), (1.0, 3.0));
+        assert_eq!(histogram.counts, vec![1, 2]);
+        assert_eq!(histogram.excluded, 3);
+    }
+
+    #[test]
+    fn counts_equal_values_in_the_first_bin() {
+        let histogram = Histogram::new([4.0; 3].into_iter(), 4, &[]);
+        assert_eq!(histogram.counts, vec![3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn parses_ignored_values() {
+        assert_eq!(
+            parse_ignored("0xFF, -1, 2.5,").unwrap(),
+            vec![255.0, -1.0, 2.5]
+        );
+        assert_eq!(parse_ignored(" ").unwrap(), vec![]);
+        assert!(parse_ignored("0xZZ").is_err());
+        assert!(parse_ignored("1, two").is_err());
+    }
+
+    #[test]
+    fn limits_the_number_of_bins() {
+        let mut options = HistogramOptions {
+            bins: MAX_BINS,
+            ..Default::default()
+        };
+        options.more_bins();
+        assert_eq!(options.bins, MAX_BINS);
+        options.bins = MIN_BINS;
+        options.fewer_bins();
+        assert_eq!(options.bins, MIN_BINS);
+    }
+}
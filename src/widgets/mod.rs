@@ -1 +1,6 @@
+pub mod fuzzy;
+pub mod heatmap;
+pub mod histogram;
+pub mod plot;
+pub mod query;
 pub mod tree;
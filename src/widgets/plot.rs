@@ -0,0 +1,162 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    text::Line,
+    widgets::{Axis, Chart, Dataset, GraphType, Widget},
+};
+use std::ops::Range;
+
+const MIN_WINDOW: usize = 8;
+const PAN_FRACTION: usize = 8;
+
+/// The range of points shown by a plot.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlotWindow {
+    start: usize,
+    /// `None` shows every point.
+    length: Option<usize>,
+    total: usize,
+}
+
+impl PlotWindow {
+    pub fn zoom_in(&mut self) {
+        let length = self.length.unwrap_or(self.total);
+        if length <= MIN_WINDOW {
+            return;
+        }
+        let zoomed = (length / 2).max(MIN_WINDOW);
+        self.start += (length - zoomed) / 2;
+        self.length = Some(zoomed);
+    }
+
+    pub fn zoom_out(&mut self) {
+        let Some(length) = self.length else {
+            return;
+        };
+        let zoomed = length.saturating_mul(2);
+        self.start = self.start.saturating_sub((zoomed - length) / 2);
+        self.length = (zoomed < self.total).then_some(zoomed);
+    }
+
+    /// Moves the window by a fraction of its length.
+    pub fn pan(&mut self, delta: isize) {
+        let length = self.length.unwrap_or(self.total);
+        let step = (length / PAN_FRACTION).max(1) as isize;
+        self.start = self.start.saturating_add_signed(delta * step);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The points shown out of the total.
+    pub fn range(&mut self, total: usize) -> Range<usize> {
+        self.total = total;
+        let length = self.length.unwrap_or(total).min(total);
+        self.start = self.start.min(total - length);
+        self.start..self.start + length
+    }
+}
+
+/// Reduces the points to the extremes of each bucket, such that spikes remain visible.
+pub fn decimate(points: Vec<(f64, f64)>, buckets: usize) -> Vec<(f64, f64)> {
+    if points.len() <= 2 * buckets || buckets == 0 {
+        return points;
+    }
+    let bucket_size = points.len().div_ceil(buckets);
+    points
+        .chunks(bucket_size)
+        .flat_map(|bucket| {
+            let finite = || bucket.iter().filter(|(_, y)| y.is_finite());
+            let min = finite().min_by(|a, b| a.1.total_cmp(&b.1));
+            let max = finite().max_by(|a, b| a.1.total_cmp(&b.1));
+            match (min, max) {
+                (Some(&min), Some(&max)) if min.0 <= max.0 => vec![min, max],
+                (Some(&min), Some(&max)) => vec![max, min],
+                _ => vec![],
+            }
+        })
+        .collect()
+}
+
+/// A line plot drawn with braille characters, scaled to fit the finite points.
+pub struct LinePlot<'a> {
+    points: &'a [(f64, f64)],
+    x_title: String,
+    color: Color,
+}
+
+impl<'a> LinePlot<'a> {
+    pub fn new(points: &'a [(f64, f64)], x_title: String, color: Color) -> Self {
+        Self {
+            points,
+            x_title,
+            color,
+        }
+    }
+}
+
+impl Widget for LinePlot<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let finite = self
+            .points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect::<Vec<_>>();
+        let x_bounds = bounds(finite.iter().map(|(x, _)| *x), 0.0);
+        let y_bounds = bounds(finite.iter().map(|(_, y)| *y), 0.05);
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::new().fg(self.color))
+            .data(&finite);
+        let chart = Chart::new(vec![dataset])
+            .x_axis(
+                Axis::default()
+                    .title(self.x_title)
+                    .bounds(x_bounds)
+                    .labels(labels(x_bounds)),
+            )
+            .y_axis(Axis::default().bounds(y_bounds).labels(labels(y_bounds)));
+        chart.render(area, buf);
+    }
+}
+
+fn bounds(values: impl Iterator<Item = f64>, padding: f64) -> [f64; 2] {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    });
+    if min > max {
+        [0.0, 1.0]
+    } else if min == max {
+        [min - 0.5, max + 0.5]
+    } else {
+        let padding = (max - min) * padding;
+        [min - padding, max + padding]
+    }
+}
+
+fn labels([min, max]: [f64; 2]) -> Vec<Line<'static>> {
+    [min, (min + max) / 2.0, max]
+        .into_iter()
+        .map(|value| Line::raw(format_number(value)))
+        .collect()
+}
+
+pub fn format_number(value: f64) -> String {
+    let magnitude = value.abs();
+    if value == 0.0 {
+        "0".to_string()
+    } else if !(1e-3..1e5).contains(&magnitude) {
+        format!("{value:.2e}")
+    } else {
+        let formatted = format!("{value:.3}");
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
@@ -0,0 +1,144 @@
+/// A condition on a property of a tree item, such as `class:NXdetector`, `ndim>2` or `size>1G`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    key: String,
+    comparison: Comparison,
+    value: String,
+}
+
+const KEYS: &[&str] = &["class", "attr", "ndim", "dtype", "size"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl QueryTerm {
+    /// Parses a term such as `key:value` or `key>=value`, returning `None` if it is not of this
+    /// form or an error if its key is unknown.
+    pub fn parse(term: &str) -> Option<Result<Self, String>> {
+        let key_length = term
+            .find(|char: char| !char.is_ascii_alphabetic())
+            .filter(|&length| length > 0)?;
+        let (key, rest) = term.split_at(key_length);
+        let (comparison, value) = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            (":", Comparison::Equal),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ]
+        .into_iter()
+        .find_map(|(operator, comparison)| {
+            rest.strip_prefix(operator).map(|value| (comparison, value))
+        })?;
+        if value.is_empty() {
+            return None;
+        }
+        let key = key.to_lowercase();
+        if !KEYS.contains(&key.as_str()) {
+            return Some(Err(format!(
+                "Unknown term '{key}', expected one of {}",
+                KEYS.join(", ")
+            )));
+        }
+        Some(Ok(Self {
+            key,
+            comparison,
+            value: value.to_string(),
+        }))
+    }
+
+    /// Whether any of the properties with the key of the term satisfy its comparison.
+    pub fn matches(&self, properties: &[(String, String)]) -> bool {
+        properties
+            .iter()
+            .filter(|(key, _)| *key == self.key)
+            .any(|(_, value)| {
+                let numbers = parse_number(value).zip(parse_number(&self.value));
+                match (self.comparison, numbers) {
+                    (Comparison::Equal, Some((value, expected))) => value == expected,
+                    (Comparison::Equal, None) => value.eq_ignore_ascii_case(&self.value),
+                    (Comparison::Greater, Some((value, expected))) => value > expected,
+                    (Comparison::GreaterOrEqual, Some((value, expected))) => value >= expected,
+                    (Comparison::Less, Some((value, expected))) => value < expected,
+                    (Comparison::LessOrEqual, Some((value, expected))) => value <= expected,
+                    (_, None) => false,
+                }
+            })
+    }
+}
+
+/// Parses a number with an optional binary size suffix, such as `1.5K` or `2GiB`.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let digits = value
+        .find(|char: char| !(char.is_ascii_digit() || matches!(char, '.' | '-' | '+' | 'e')))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(digits);
+    let multiplier = match suffix.to_ascii_uppercase().as_str() {
+        "" | "B" => 1u64,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return None,
+    };
+    Some(number.parse::<f64>().ok()? * multiplier as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties(key: &str, value: &str) -> Vec<(String, String)> {
+        vec![(key.to_string(), value.to_string())]
+    }
+
+    fn term(term: &str) -> QueryTerm {
+        QueryTerm::parse(term).unwrap().unwrap()
+    }
+
+    #[test]
+    fn parses_terms_with_known_keys() {
+        assert!(term("class:NXdetector").matches(&properties("class", "nxdetector")));
+        assert!(term("Class:NXdetector").matches(&properties("class", "NXdetector")));
+        assert!(!term("class:NXdetector").matches(&properties("class", "NXsample")));
+        assert!(term("ndim>=2").matches(&properties("ndim", "2")));
+        assert!(!term("ndim<2").matches(&properties("ndim", "2")));
+    }
+
+    #[test]
+    fn compares_sizes_with_suffixes() {
+        assert!(term("size>1G").matches(&properties("size", "2147483648")));
+        assert!(!term("size>1G").matches(&properties("size", "1073741824")));
+        assert!(term("size<=1k").matches(&properties("size", "1024")));
+        assert!(!term("size>1G").matches(&properties("dtype", "2147483648")));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(QueryTerm::parse("clas:NXdetector").unwrap().is_err());
+    }
+
+    #[test]
+    fn leaves_other_terms_to_path_matching() {
+        assert_eq!(QueryTerm::parse("data"), None);
+        assert_eq!(QueryTerm::parse("size>"), None);
+        assert_eq!(QueryTerm::parse(":data"), None);
+    }
+
+    #[test]
+    fn parses_numbers_with_size_suffixes() {
+        assert_eq!(parse_number("512"), Some(512.0));
+        assert_eq!(parse_number("1.5K"), Some(1536.0));
+        assert_eq!(parse_number("2GiB"), Some(2.0 * (1u64 << 30) as f64));
+        assert_eq!(parse_number("-1.5e3"), Some(-1500.0));
+        assert_eq!(parse_number("1x"), None);
+        assert_eq!(parse_number("G"), None);
+    }
+}
@@ -1,12 +1,14 @@
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Alignment, Position, Rect},
     style::{Color, Modifier, Style},
-    text::Text,
+    text::{Span, Text},
     widgets::{Block, Paragraph, StatefulWidget, Widget},
 };
 use regex::Regex;
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::OnceCell, collections::HashSet, fmt::Display};
+
+use super::{fuzzy::fuzzy_match, query::QueryTerm};
 
 #[derive(Debug, Clone)]
 pub struct TreeItem<'a> {
@@ -14,6 +16,11 @@ pub struct TreeItem<'a> {
     color: Color,
     children: Vec<TreeItem<'a>>,
     expanded: bool,
+    expandable: bool,
+    properties: Vec<(String, String)>,
+    glyph: Option<String>,
+    annotation: Option<Span<'a>>,
+    emphasized: bool,
 }
 
 impl<'a> TreeItem<'a> {
@@ -22,19 +29,167 @@ impl<'a> TreeItem<'a> {
             contents,
             color,
             children,
-            expanded: true,
+            expanded: false,
+            expandable: false,
+            properties: Vec::new(),
+            glyph: None,
+            annotation: None,
+            emphasized: false,
         }
     }
+
+    /// Shows an expander even if the item has no children, such as before they are loaded.
+    #[must_use]
+    pub fn expandable(mut self, expandable: bool) -> Self {
+        self.expandable = expandable;
+        self
+    }
+
+    fn has_expander(&self) -> bool {
+        self.expandable || !self.children.is_empty()
+    }
+
+    /// Sets the properties which search terms such as `key:value` are compared against.
+    #[must_use]
+    pub fn properties(mut self, properties: Vec<(String, String)>) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    #[must_use]
+    pub fn glyph(mut self, glyph: Option<String>) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    /// Sets text shown after the contents, which searches do not match.
+    #[must_use]
+    pub fn annotation(mut self, annotation: Option<Span<'a>>) -> Self {
+        self.annotation = annotation;
+        self
+    }
+
+    #[must_use]
+    pub fn emphasized(mut self, emphasized: bool) -> Self {
+        self.emphasized = emphasized;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
-struct ComputedItem<'a> {
-    item: &'a TreeItem<'a>,
+struct ComputedItem {
     index: Vec<usize>,
     visible: bool,
-    search_candidate: bool,
+    height: usize,
+    search_match: Option<SearchMatch>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchKind {
+    #[default]
+    Regex,
+    Fuzzy,
+}
+
+impl Display for SearchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Regex => write!(f, "regex"),
+            Self::Fuzzy => write!(f, "fuzzy"),
+        }
+    }
+}
+
+/// A compiled search, in which terms such as `key:value` are conditions on the properties of
+/// each item, and all other terms are matched against its path.
+#[derive(Debug, Clone)]
+struct Matcher {
+    text: Option<TextMatcher>,
+    terms: Vec<QueryTerm>,
+}
+
+#[derive(Debug, Clone)]
+enum TextMatcher {
+    Regex(Regex),
+    Fuzzy(String),
+}
+
+impl Matcher {
+    fn new(search: &str, kind: SearchKind) -> Result<Self, String> {
+        let mut terms = Vec::new();
+        let mut words = Vec::new();
+        for word in search.split_whitespace() {
+            match QueryTerm::parse(word) {
+                Some(term) => terms.push(term?),
+                None => words.push(word),
+            }
+        }
+        let text = words.join(" ");
+        let text = match kind {
+            _ if text.is_empty() => None,
+            SearchKind::Regex => Some(TextMatcher::Regex(Regex::new(&text).map_err(|err| {
+                let message = err.to_string();
+                message
+                    .lines()
+                    .last()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            })?)),
+            SearchKind::Fuzzy => Some(TextMatcher::Fuzzy(text)),
+        };
+        Ok(Self { text, terms })
+    }
+
+    fn find(&self, text: &str, properties: &[(String, String)]) -> Option<SearchMatch> {
+        if !self.terms.iter().all(|term| term.matches(properties)) {
+            return None;
+        }
+        match &self.text {
+            None => Some(SearchMatch {
+                score: 0,
+                positions: Vec::new(),
+            }),
+            Some(TextMatcher::Regex(regex)) => {
+                let ranges = regex
+                    .find_iter(text)
+                    .map(|found| found.range())
+                    .collect::<Vec<_>>();
+                if ranges.is_empty() {
+                    return None;
+                }
+                let positions = text
+                    .char_indices()
+                    .enumerate()
+                    .filter(|(_, (byte, _))| ranges.iter().any(|range| range.contains(byte)))
+                    .map(|(position, _)| position)
+                    .collect();
+                Some(SearchMatch {
+                    score: 0,
+                    positions,
+                })
+            }
+            Some(TextMatcher::Fuzzy(pattern)) => {
+                fuzzy_match(pattern, text).map(|found| SearchMatch {
+                    score: found.score,
+                    positions: found.positions,
+                })
+            }
+        }
+    }
 }
 
+#[derive(Debug, Clone)]
+struct SearchMatch {
+    score: i64,
+    positions: Vec<usize>,
+}
+
+/// The width of the expander, which is also the indent of each level.
+const EXPANDER_WIDTH: u16 = 2;
+
+const DEFAULT_MATCH_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
 #[derive(Debug, Clone)]
 pub struct TreeState<'a> {
     items: Vec<TreeItem<'a>>,
@@ -42,6 +197,14 @@ pub struct TreeState<'a> {
     start: usize,
     end: usize,
     search: Option<String>,
+    search_kind: SearchKind,
+    matcher: Option<Result<Matcher, String>>,
+    search_confirmed: bool,
+    filter: bool,
+    loading: Option<usize>,
+    /// The flattened items, cleared whenever the items, the search or the filter change.
+    computed: OnceCell<Vec<ComputedItem>>,
+    area: Rect,
 }
 
 impl<'a> TreeState<'a> {
@@ -52,6 +215,13 @@ impl<'a> TreeState<'a> {
             start: Default::default(),
             end: Default::default(),
             search: Default::default(),
+            search_kind: Default::default(),
+            matcher: Default::default(),
+            search_confirmed: Default::default(),
+            filter: Default::default(),
+            loading: Default::default(),
+            computed: Default::default(),
+            area: Default::default(),
         }
     }
 
@@ -64,10 +234,13 @@ impl<'a> TreeState<'a> {
     }
 
     pub fn move_down(&mut self) {
-        self.position = self
-            .position
-            .saturating_add(1)
-            .min(self.items().iter().filter(|item| item.visible).count() - 1);
+        self.position = self.position.saturating_add(1).min(
+            self.items()
+                .iter()
+                .filter(|item| item.visible)
+                .count()
+                .saturating_sub(1),
+        );
     }
 
     pub fn move_up(&mut self) {
@@ -77,25 +250,79 @@ impl<'a> TreeState<'a> {
     pub fn page_down(&mut self) {
         self.position = self
             .position
-            .saturating_add(self.end - self.start - 1)
-            .min(self.items().iter().filter(|item| item.visible).count() - 1);
+            .saturating_add((self.end - self.start).saturating_sub(1))
+            .min(
+                self.items()
+                    .iter()
+                    .filter(|item| item.visible)
+                    .count()
+                    .saturating_sub(1),
+            );
     }
 
     pub fn page_up(&mut self) {
-        self.position = self.position.saturating_sub(self.end - self.start - 1);
+        self.position = self
+            .position
+            .saturating_sub((self.end - self.start).saturating_sub(1));
+    }
+
+    fn item(&self, index: &[usize]) -> Option<&TreeItem<'a>> {
+        let mut indices = index.iter();
+        let mut item = self.items.get(*indices.next()?)?;
+        for &idx in indices {
+            item = item.children.get(idx)?;
+        }
+        Some(item)
+    }
+
+    fn item_mut(&mut self, index: &[usize]) -> Option<&mut TreeItem<'a>> {
+        self.computed.take();
+        let mut indices = index.iter();
+        let mut item = self.items.get_mut(*indices.next()?)?;
+        for &idx in indices {
+            item = item.children.get_mut(idx)?;
+        }
+        Some(item)
     }
 
     fn selected_mut(&mut self) -> Option<&mut TreeItem<'a>> {
-        if let Some(index) = self.position() {
-            let mut indidecs = index.into_iter();
-            let mut item = self.items.get_mut(indidecs.next()?)?;
-            for idx in indidecs {
-                item = item.children.get_mut(idx)?;
+        let index = self.position()?;
+        self.item_mut(&index)
+    }
+
+    /// Selects the item at the index, expanding each of its ancestors.
+    pub fn select(&mut self, index: &[usize]) {
+        for depth in 1..index.len() {
+            if let Some(item) = self.item_mut(&index[..depth]) {
+                item.expanded = true;
             }
-            Some(item)
-        } else {
-            None
         }
+        let position = self
+            .items()
+            .iter()
+            .filter(|item| item.visible)
+            .position(|item| item.index == index);
+        if let Some(position) = position {
+            self.position = position;
+        }
+    }
+
+    pub fn set_items(&mut self, items: Vec<TreeItem<'a>>) {
+        self.items = items;
+        self.computed.take();
+    }
+
+    pub fn set_children(&mut self, index: &[usize], children: Vec<TreeItem<'a>>) {
+        let selected = self.position();
+        if let Some(item) = self.item_mut(index) {
+            item.children = children;
+        }
+        self.retain_selection(selected);
+    }
+
+    /// Sets the number of groups loaded for the search while more remain.
+    pub fn set_loading(&mut self, loading: Option<usize>) {
+        self.loading = loading;
     }
 
     pub fn expand(&mut self) {
@@ -104,7 +331,40 @@ impl<'a> TreeState<'a> {
         }
     }
 
+    pub fn toggle(&mut self) {
+        if let Some(selected) = self.selected_mut() {
+            selected.expanded = !selected.expanded;
+        }
+    }
+
+    /// Selects the item at the position on screen, returning whether its expander was clicked.
+    pub fn click(&mut self, column: u16, row: u16) -> Option<bool> {
+        if !self.area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let area = self.area;
+        let mut item_top = area.top();
+        let (position, expander) = self
+            .items()
+            .iter()
+            .filter(|item| item.visible)
+            .enumerate()
+            .take(self.end)
+            .skip(self.start)
+            .find_map(|(item_idx, item)| {
+                let height = item.height as u16;
+                let clicked = (item_top..item_top + height).contains(&row);
+                item_top += height;
+                let expander = area.left() + EXPANDER_WIDTH * (item.index.len() as u16 - 1);
+                let has_expander = self.item(&item.index).is_some_and(TreeItem::has_expander);
+                clicked.then_some((item_idx, has_expander && column == expander))
+            })?;
+        self.position = position;
+        Some(expander)
+    }
+
     pub fn expand_all(&mut self) {
+        self.computed.take();
         let mut to_collapse = self.items.iter_mut().collect::<Vec<_>>();
         while let Some(item) = to_collapse.pop() {
             to_collapse.extend(item.children.iter_mut());
@@ -119,6 +379,7 @@ impl<'a> TreeState<'a> {
     }
 
     pub fn collapse_all(&mut self) {
+        self.computed.take();
         let mut to_collapse = self.items.iter_mut().collect::<Vec<_>>();
         while let Some(item) = to_collapse.pop() {
             to_collapse.extend(item.children.iter_mut());
@@ -128,16 +389,123 @@ impl<'a> TreeState<'a> {
     }
 
     pub fn search(&mut self, search: Option<String>) {
+        let selected = self.position();
+        self.matcher = search
+            .as_ref()
+            .map(|search| Matcher::new(search, self.search_kind));
         self.search = search;
+        self.search_confirmed = false;
+        self.computed.take();
+        self.retain_selection(selected);
+    }
+
+    pub fn toggle_search_kind(&mut self) {
+        self.search_kind = match self.search_kind {
+            SearchKind::Regex => SearchKind::Fuzzy,
+            SearchKind::Fuzzy => SearchKind::Regex,
+        };
+        let search_confirmed = self.search_confirmed;
+        self.search(self.search.clone());
+        self.search_confirmed = search_confirmed;
+    }
+
+    /// Switches between highlighting matches and hiding the items which do not lead to one.
+    pub fn toggle_filter(&mut self) {
+        let selected = self.position();
+        self.filter = !self.filter;
+        self.computed.take();
+        self.retain_selection(selected);
+    }
+
+    fn retain_selection(&mut self, selected: Option<Vec<usize>>) {
+        let visible = self
+            .items()
+            .iter()
+            .filter(|item| item.visible)
+            .map(|item| &item.index)
+            .collect::<Vec<_>>();
+        self.position = selected
+            .and_then(|selected| visible.iter().position(|index| **index == selected))
+            .unwrap_or(self.position.min(visible.len().saturating_sub(1)));
     }
 
-    fn items(&'a self) -> Vec<ComputedItem<'a>> {
-        let search_regex = self.search.clone().map(|search| Regex::new(&search));
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Finishes editing the search and moves the cursor to the best match.
+    pub fn confirm_search(&mut self) {
+        self.search_confirmed = true;
+        if let Some(first) = self.ranked_matches().first() {
+            self.select(first);
+        }
+    }
+
+    /// Moves the cursor to the next best match, wrapping around after the worst.
+    pub fn next_match(&mut self) {
+        self.jump_to_match(false)
+    }
+
+    /// Moves the cursor to the previous better match, wrapping around before the best.
+    pub fn previous_match(&mut self) {
+        self.jump_to_match(true)
+    }
+
+    fn jump_to_match(&mut self, backwards: bool) {
+        let matches = self.ranked_matches();
+        if matches.is_empty() {
+            return;
+        }
+        let current = self
+            .position()
+            .and_then(|selected| matches.iter().position(|index| *index == selected));
+        let target = match (current, backwards) {
+            (Some(current), false) => (current + 1) % matches.len(),
+            (Some(current), true) => (current + matches.len() - 1) % matches.len(),
+            (None, false) => 0,
+            (None, true) => matches.len() - 1,
+        };
+        self.select(&matches[target]);
+    }
+
+    /// The indices of all matching items, from the best to the worst match.
+    fn ranked_matches(&self) -> Vec<Vec<usize>> {
+        let mut matches = self
+            .items()
+            .iter()
+            .filter_map(|item| {
+                let found = item.search_match.as_ref()?;
+                Some((found.score, item.index.clone()))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// The one-based rank of the match under the cursor and the total number of matches.
+    fn match_counts(&self) -> (Option<usize>, usize) {
+        let matches = self.ranked_matches();
+        let current = self
+            .position()
+            .and_then(|selected| matches.iter().position(|index| *index == selected))
+            .map(|idx| idx + 1);
+        (current, matches.len())
+    }
+
+    fn items(&self) -> &[ComputedItem] {
+        self.computed.get_or_init(|| self.compute_items())
+    }
+
+    fn compute_items(&self) -> Vec<ComputedItem> {
+        let matcher = self
+            .matcher
+            .as_ref()
+            .and_then(|matcher| matcher.as_ref().ok());
         let mut to_flatten = self
             .items
             .iter()
             .enumerate()
-            .map(|(index, item)| (vec![index], true, "/".to_string(), item))
+            .map(|(index, item)| (vec![index], true, String::new(), item))
             .collect::<Vec<_>>();
         let mut entries = Vec::default();
         while let Some((index, visible, prefix, item)) = to_flatten.pop() {
@@ -149,16 +517,22 @@ impl<'a> TreeState<'a> {
                 .collect::<Vec<Cow<str>>>()
                 .join("");
             let text = format!("{prefix}/{name}");
-            let search_candidate = if let Some(Ok(search_regex)) = search_regex.clone() {
-                search_regex.is_match(&text)
-            } else {
-                false
-            };
+            let name_start = prefix.chars().count() + 1;
+            let search_match = matcher
+                .and_then(|matcher| matcher.find(&text, &item.properties))
+                .map(|found| SearchMatch {
+                    score: found.score,
+                    positions: found
+                        .positions
+                        .into_iter()
+                        .filter_map(|position| position.checked_sub(name_start))
+                        .collect(),
+                });
             entries.push(ComputedItem {
-                item,
                 index: index.clone(),
                 visible,
-                search_candidate,
+                height: item.contents.height(),
+                search_match,
             });
             to_flatten.extend(
                 item.children
@@ -179,20 +553,36 @@ impl<'a> TreeState<'a> {
                     .rev(),
             );
         }
+        if self.filter && matcher.is_some() {
+            let shown = entries
+                .iter()
+                .filter(|entry| entry.search_match.is_some())
+                .flat_map(|entry| (1..=entry.index.len()).map(|depth| &entry.index[..depth]))
+                .map(<[usize]>::to_vec)
+                .collect::<HashSet<_>>();
+            for entry in entries.iter_mut() {
+                entry.visible = shown.contains(&entry.index);
+            }
+        }
         entries
     }
 
     fn update_bounds(&mut self, max_height: usize) {
         let heights = self
             .items()
-            .into_iter()
+            .iter()
             .filter(|item| item.visible)
             .scan(0, |acc, item| {
-                *acc += item.item.contents.height();
+                *acc += item.height;
                 Some(*acc)
             })
             .collect::<Vec<_>>();
 
+        if heights.is_empty() {
+            (self.start, self.end) = (0, 0);
+            return;
+        }
+
         if self.position < self.start {
             self.start = self.position;
             self.end = heights
@@ -224,10 +614,24 @@ impl<'a> TreeState<'a> {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Tree<'i> {
     style: Style,
     block: Option<Block<'i>>,
+    /// Defaults to the color of the selected item.
+    selection_color: Option<Color>,
+    match_style: Style,
+}
+
+impl Default for Tree<'_> {
+    fn default() -> Self {
+        Self {
+            style: Style::default(),
+            block: None,
+            selection_color: None,
+            match_style: DEFAULT_MATCH_STYLE,
+        }
+    }
 }
 
 impl<'i> Tree<'i> {
@@ -236,6 +640,18 @@ impl<'i> Tree<'i> {
         self.block = Some(block);
         self
     }
+
+    #[must_use]
+    pub fn selection_color(mut self, color: Option<Color>) -> Self {
+        self.selection_color = color;
+        self
+    }
+
+    #[must_use]
+    pub fn match_style(mut self, style: Style) -> Self {
+        self.match_style = style;
+        self
+    }
 }
 
 impl<'a> StatefulWidget for Tree<'a> {
@@ -251,9 +667,10 @@ impl<'a> StatefulWidget for Tree<'a> {
         });
 
         state.update_bounds(inner_area.height as usize);
+        state.area = inner_area;
 
         let mut item_bottom = inner_area.top();
-        for (item_idx, item) in state
+        for (item_idx, computed) in state
             .items()
             .iter()
             .filter(|item| item.visible)
@@ -261,46 +678,203 @@ impl<'a> StatefulWidget for Tree<'a> {
             .take(state.end)
             .skip(state.start)
         {
+            let Some(item) = state.item(&computed.index) else {
+                continue;
+            };
             let item_top = item_bottom;
-            let indent = 2 * (item.index.len() as u16 - 1);
+            let indent = EXPANDER_WIDTH * (computed.index.len() as u16 - 1);
+            if item.has_expander() && indent < inner_area.width {
+                let expander = if item.expanded {
+                    "\u{25be}"
+                } else {
+                    "\u{25b8}"
+                };
+                buf.set_string(
+                    inner_area.left() + indent,
+                    item_top,
+                    expander,
+                    Style::new().fg(item.color),
+                );
+            }
+            let indent = indent + EXPANDER_WIDTH;
+            let glyph_width = match item.glyph.as_deref() {
+                Some(glyph) if indent < inner_area.width => {
+                    buf.set_string(
+                        inner_area.left() + indent,
+                        item_top,
+                        glyph,
+                        Style::new().fg(item.color),
+                    );
+                    Span::raw(glyph).width() as u16 + 1
+                }
+                _ => 0,
+            };
+            let indent = indent + glyph_width;
             let area = Rect::new(
                 inner_area.left() + indent,
                 item_top,
-                inner_area.width - indent,
-                item.item.contents.height() as u16,
+                inner_area.width.saturating_sub(indent),
+                item.contents.height() as u16,
             );
-            let style = if item_idx == state.position && state.search.is_none() {
+            let editing_search = state.search.is_some() && !state.search_confirmed;
+            let style = if item_idx == state.position && !editing_search {
                 Style::new()
-                    .bg(item.item.color)
+                    .bg(self.selection_color.unwrap_or(item.color))
                     .add_modifier(Modifier::BOLD)
-            } else if item.search_candidate {
+            } else if computed.search_match.is_some() {
                 Style::new()
-                    .fg(item.item.color)
+                    .fg(item.color)
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED)
+            } else if item.emphasized {
+                Style::new().fg(item.color).add_modifier(Modifier::BOLD)
             } else {
-                Style::new().fg(item.item.color)
+                Style::new().fg(item.color)
             };
 
-            for (line_idx, line) in item.item.contents.lines.iter().enumerate() {
+            for (line_idx, line) in item.contents.lines.iter().enumerate() {
                 let text_area = Rect::new(area.left(), item_top, line.width() as u16, 1);
                 buf.set_style(text_area, style);
                 buf.set_line(area.left(), item_top + line_idx as u16, line, area.width);
             }
-            item_bottom += item.item.contents.height() as u16;
+            if let (Some(annotation), Some(line)) =
+                (item.annotation.as_ref(), item.contents.lines.last())
+            {
+                let left = area.left().saturating_add(line.width() as u16);
+                let top = item_top + item.contents.lines.len() as u16 - 1;
+                if left < area.right() {
+                    let width = area.right() - left;
+                    buf.set_style(
+                        Rect::new(left, top, (annotation.width() as u16).min(width), 1),
+                        style,
+                    );
+                    buf.set_span(left, top, annotation, width);
+                }
+            }
+            if let Some(search_match) = computed.search_match.as_ref() {
+                let mut line_start = 0;
+                for (line_idx, line) in item.contents.lines.iter().enumerate() {
+                    let line_length = line
+                        .spans
+                        .iter()
+                        .map(|span| span.content.chars().count())
+                        .sum::<usize>();
+                    for position in &search_match.positions {
+                        if let Some(column) = position
+                            .checked_sub(line_start)
+                            .filter(|&column| column < line_length && column < area.width as usize)
+                        {
+                            buf.set_style(
+                                Rect::new(
+                                    area.left() + column as u16,
+                                    item_top + line_idx as u16,
+                                    1,
+                                    1,
+                                ),
+                                self.match_style,
+                            );
+                        }
+                    }
+                    line_start += line_length;
+                }
+            }
+            item_bottom += item.contents.height() as u16;
         }
 
         if let Some(search) = state.search.as_ref() {
             let search_area = Rect::new(area.left() + 1, area.bottom() - 1, area.width - 1, 1);
-            let search_text = format!("\u{f002} {search}");
-            let cursor_area = Rect::new(
-                search_area.left() + search_text.chars().count() as u16,
+            let icon = if state.filter { '\u{f0b0}' } else { '\u{f002}' };
+            let search_text = format!("{icon} {search}");
+            if !state.search_confirmed {
+                let cursor_area = Rect::new(
+                    search_area.left() + search_text.chars().count() as u16,
+                    search_area.top(),
+                    1,
+                    1,
+                );
+                buf.set_style(cursor_area, Style::new().bg(Color::White));
+            }
+            Paragraph::new(search_text).render(search_area, buf);
+            let kind = state.search_kind;
+            let (count_text, count_style) =
+                match (state.matcher.as_ref(), state.loading, state.match_counts()) {
+                    (Some(Err(err)), _, _) => (format!(" {err} "), Style::new().fg(Color::Red)),
+                    (_, Some(loaded), _) => (
+                        format!(" searching\u{2026} ({loaded} groups loaded) "),
+                        Style::new(),
+                    ),
+                    (_, None, (Some(current), total)) => {
+                        (format!(" {current}/{total} {kind} matches "), Style::new())
+                    }
+                    (_, None, (None, total)) => (format!(" {total} {kind} matches "), Style::new()),
+                };
+            let count_area = Rect::new(
+                search_area.left(),
                 search_area.top(),
-                1,
+                search_area.width.saturating_sub(1),
                 1,
             );
-            buf.set_style(cursor_area, Style::new().bg(Color::White));
-            Paragraph::new(search_text).render(search_area, buf);
+            Paragraph::new(count_text)
+                .style(count_style)
+                .alignment(Alignment::Right)
+                .render(count_area, buf);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &'static str, children: Vec<TreeItem<'static>>) -> TreeItem<'static> {
+        TreeItem::new(Text::raw(name), Color::Reset, children)
+    }
+
+    fn state() -> TreeState<'static> {
+        TreeState::new(vec![item(
+            "entry",
+            vec![item("data", vec![]), item("sample", vec![])],
+        )])
+    }
+
+    fn visible(state: &TreeState) -> Vec<Vec<usize>> {
+        state
+            .items()
+            .iter()
+            .filter(|item| item.visible)
+            .map(|item| item.index.clone())
+            .collect()
+    }
+
+    #[test]
+    fn updates_visible_items_as_groups_are_expanded() {
+        let mut state = state();
+        assert_eq!(visible(&state), vec![vec![0]]);
+        state.expand();
+        assert_eq!(visible(&state), vec![vec![0], vec![0, 0], vec![0, 1]]);
+        state.move_down();
+        assert_eq!(state.position(), Some(vec![0, 0]));
+        state.collapse_all();
+        assert_eq!(visible(&state), vec![vec![0]]);
+    }
+
+    #[test]
+    fn updates_matches_as_the_search_and_items_change() {
+        let mut state = state();
+        state.search(Some("sample".to_string()));
+        assert_eq!(state.match_counts(), (None, 1));
+        state.set_children(&[0, 0], vec![item("sample", vec![])]);
+        assert_eq!(state.match_counts(), (None, 2));
+        state.toggle_filter();
+        assert_eq!(
+            visible(&state),
+            vec![vec![0], vec![0, 0], vec![0, 0, 0], vec![0, 1]]
+        );
+        state.confirm_search();
+        assert_eq!(state.position(), Some(vec![0, 0, 0]));
+        state.next_match();
+        assert_eq!(state.position(), Some(vec![0, 1]));
+        state.search(None);
+        assert_eq!(state.match_counts(), (None, 0));
+    }
+}